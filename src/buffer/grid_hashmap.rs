@@ -1,90 +1,311 @@
-
 use {
+    crate::{
+        view::{
+            InnerViewPort, OuterViewPort, ViewPort, View,
+            grid::GridView,
+            index::{IndexArea, IndexView},
+            transaction,
+        },
+    },
+    cgmath::Point2,
+    std::sync::RwLock,
     std::{
-        sync::Arc,
+        cmp::max,
         collections::HashMap,
-        hash::Hash
+        iter::FromIterator,
+        ops::{Deref, DerefMut},
+        sync::Arc,
     },
-    std::sync::RwLock,
-    crate::{
-        core::{
-            Observer,
-            ObserverBroadcast,
-            View,
-            InnerViewPort
-        },
-        index::{IndexArea, IndexView}
-    }
 };
 
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
-struct GridBuffer<Item> {
-    data: HashMap<Point2<i16>, Item>,
-    limit: Point2<i16>
+pub struct GridBufferView<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    data: Arc<RwLock<HashMap<Point2<i16>, Item>>>,
+    limit: Arc<RwLock<Point2<i16>>>,
 }
 
-impl<Item> View for GridBuffer<Item>
-where Item: Clone + Send + Sync + 'static
+impl<Item> View for GridBufferView<Item>
+where
+    Item: Clone + Send + Sync + 'static,
 {
     type Msg = IndexArea<Point2<i16>>;
 }
 
 impl<Item> IndexView<Point2<i16>> for GridBufferView<Item>
-where Item: Clone + Send + Sync + 'static
+where
+    Item: Clone + Send + Sync + 'static,
 {
     type Item = Item;
 
     fn get(&self, key: &Point2<i16>) -> Option<Self::Item> {
-        self.data.get(key).cloned()
+        self.data.read().unwrap().get(key).cloned()
     }
 
     fn area(&self) -> IndexArea<Point2<i16>> {
-        IndexArea::Range(
-            Point2::new(0, 0)
-                ..= self.limit
-        )
+        IndexArea::Range(Point2::new(0, 0)..=*self.limit.read().unwrap())
     }
 }
 
-pub struct GridBufferController<Item>
-where Item: Clone + Send + Sync + 'static
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Grid of items, stored sparsely in a `HashMap`.
+/// The area of the grid always spans from the origin
+/// to the maximum coordinates of all contained cells.
+#[derive(Clone)]
+pub struct GridBuffer<Item>
+where
+    Item: Clone + Send + Sync + 'static,
 {
     data: Arc<RwLock<HashMap<Point2<i16>, Item>>>,
-    cast: Arc<RwLock<ObserverBroadcast<dyn IndexView<Point2<i16>, Item = Item>>>>
+    limit: Arc<RwLock<Point2<i16>>>,
+    port: InnerViewPort<dyn GridView<Item = Item>>,
+}
+
+impl<Item> FromIterator<(Point2<i16>, Item)> for GridBuffer<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn from_iter<T>(iter: T) -> Self
+    where T: IntoIterator<Item = (Point2<i16>, Item)> {
+        let mut buf = GridBuffer::new();
+        buf.insert_iter(iter);
+        buf
+    }
+}
+
+impl<Item> Default for GridBuffer<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        GridBuffer::new()
+    }
 }
 
-impl<Key, Item> GridBuffer<Key, Item>
-where Key: Clone + Hash + Eq + Send + Sync + 'static,
-      Item: Clone + Send + Sync + 'static
+impl<Item> GridBuffer<Item>
+where
+    Item: Clone + Send + Sync + 'static,
 {
-    pub fn new(port: InnerViewPort<dyn IndexView<Point2<i16>, Item = Item>>) -> Self {
+    pub fn with_port(port: InnerViewPort<dyn GridView<Item = Item>>) -> Self {
         let data = Arc::new(RwLock::new(HashMap::<Point2<i16>, Item>::new()));
-        port.set_view(Some(Arc::new(GridBufferView(data.clone()))));
+        let limit = Arc::new(RwLock::new(Point2::new(-1, -1)));
+        port.set_view(Some(Arc::new(GridBufferView {
+            data: data.clone(),
+            limit: limit.clone(),
+        })));
 
         GridBuffer {
             data,
-            cast: port.get_broadcast()
+            limit,
+            port
         }
     }
 
-    pub fn insert(&mut self, key: Point2<i16>, item: Item) {
-        self.data.write().unwrap().insert(key.clone(), item);
+    pub fn new() -> Self {
+        GridBuffer::with_port(ViewPort::new().into_inner())
+    }
 
-        if
-        
-        self.cast.notify(&IndexArea::Set(vec![ key ]));
+    pub fn get_port(&self) -> OuterViewPort<dyn GridView<Item = Item>> {
+        self.port.0.outer()
+    }
+
+    pub fn get(&self, key: &Point2<i16>) -> Option<Item> {
+        self.data.read().unwrap().get(key).cloned()
+    }
+
+    pub fn get_mut(&mut self, key: &Point2<i16>) -> MutableGridAccess<Item> {
+        MutableGridAccess {
+            buf: self.clone(),
+            key: *key,
+            val: self.get(key)
+        }
+    }
+
+    pub fn limit(&self) -> Point2<i16> {
+        *self.limit.read().unwrap()
+    }
+
+    pub fn update(&mut self, key: Point2<i16>, item: Option<Item>) {
+        transaction::save(&self.data);
+        transaction::save(&self.limit);
+        let old_limit = self.limit();
+        let new_limit = if let Some(item) = item {
+            self.data.write().unwrap().insert(key, item);
+            Point2::new(max(old_limit.x, key.x), max(old_limit.y, key.y))
+        } else if self.data.write().unwrap().remove(&key).is_some()
+            && (key.x == old_limit.x || key.y == old_limit.y)
+        {
+            // only a cell on the boundary can shrink the limit
+            self.bounds()
+        } else {
+            old_limit
+        };
+
+        self.set_limit(new_limit);
+        self.port.notify(&IndexArea::Set(vec![key]));
+    }
+
+    pub fn insert(&mut self, key: Point2<i16>, item: Item) {
+        self.update(key, Some(item));
     }
 
     pub fn insert_iter<T>(&mut self, iter: T)
-    where T: IntoIterator<Item = (Point2<i16>, Item)> {
+    where
+        T: IntoIterator<Item = (Point2<i16>, Item)>,
+    {
         for (key, item) in iter {
             self.insert(key, item);
         }
     }
 
     pub fn remove(&mut self, key: Point2<i16>) {
-        self.data.write().unwrap().remove(&key);
-        self.cast.notify(&IndexArea::Set(vec![ key ]));
+        self.update(key, None);
+    }
+
+    pub fn clear(&mut self) {
+        transaction::save(&self.data);
+        transaction::save(&self.limit);
+        let keys = self.data.read().unwrap().keys().cloned().collect();
+        self.data.write().unwrap().clear();
+
+        self.set_limit(Point2::new(-1, -1));
+        self.port.notify(&IndexArea::Set(keys));
+    }
+
+    /// bounding limit of all cells
+    fn bounds(&self) -> Point2<i16> {
+        let data = self.data.read().unwrap();
+        Point2::new(
+            data.keys().map(|pt| pt.x).max().unwrap_or(-1),
+            data.keys().map(|pt| pt.y).max().unwrap_or(-1),
+        )
+    }
+
+    /// notify the area between old and new bounds
+    fn set_limit(&mut self, new_limit: Point2<i16>) {
+        let old_limit = std::mem::replace(&mut *self.limit.write().unwrap(), new_limit);
+        if old_limit != new_limit {
+            self.port.notify(&IndexArea::Range(
+                Point2::new(0, 0)
+                    ..=Point2::new(
+                        max(old_limit.x, new_limit.x),
+                        max(old_limit.y, new_limit.y),
+                    ),
+            ));
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct MutableGridAccess<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    buf: GridBuffer<Item>,
+    key: Point2<i16>,
+    val: Option<Item>,
+}
+
+impl<Item> Deref for MutableGridAccess<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    type Target = Option<Item>;
+
+    fn deref(&self) -> &Option<Item> {
+        &self.val
+    }
+}
+
+impl<Item> DerefMut for MutableGridAccess<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.val
+    }
+}
+
+impl<Item> Drop for MutableGridAccess<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn drop(&mut self) {
+        self.buf.update(self.key, self.val.clone());
     }
 }
 
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::grid_hashmap::*,
+        view::port::UpdateTask,
+    };
+
+    #[test]
+    fn grid_buffer1() {
+        let mut buf = GridBuffer::<char>::new();
+        let view = buf.get_port().get_view().unwrap();
+
+        assert_eq!(view.area().iter().count(), 0);
+
+        buf.insert(Point2::new(0, 0), 'a');
+        buf.insert(Point2::new(2, 1), 'b');
+
+        assert_eq!(buf.limit(), Point2::new(2, 1));
+        assert_eq!(view.area().range(), Point2::new(0, 0)..=Point2::new(2, 1));
+        assert_eq!(view.get(&Point2::new(2, 1)), Some('b'));
+        assert_eq!(view.get(&Point2::new(1, 1)), None);
+
+        *buf.get_mut(&Point2::new(0, 0)) = Some('c');
+        assert_eq!(view.get(&Point2::new(0, 0)), Some('c'));
+
+        // removing an inner cell keeps the limit
+        buf.insert(Point2::new(1, 3), 'd');
+        buf.remove(Point2::new(0, 0));
+        assert_eq!(buf.limit(), Point2::new(2, 3));
+
+        buf.remove(Point2::new(1, 3));
+        assert_eq!(buf.limit(), Point2::new(2, 1));
+        buf.remove(Point2::new(2, 1));
+        assert_eq!(buf.limit(), Point2::new(-1, -1));
+
+        buf.clear();
+        assert_eq!(view.area().iter().count(), 0);
+    }
+
+    #[test]
+    fn grid_buffer_flatten() {
+        let mut top = GridBuffer::new();
+        let flat_port = top.get_port().flatten();
+
+        let b1: GridBuffer<char> = vec![
+            (Point2::new(0, 0), 'a'),
+            (Point2::new(1, 0), 'b'),
+        ].into_iter().collect();
+        let mut b2: GridBuffer<char> = vec![
+            (Point2::new(0, 0), 'c'),
+        ].into_iter().collect();
+
+        top.insert(Point2::new(0, 0), b1.get_port());
+        top.insert(Point2::new(1, 0), b2.get_port());
+
+        flat_port.0.update();
+        let flat = flat_port.get_view().unwrap();
+        assert_eq!(flat.area().range(), Point2::new(0, 0)..=Point2::new(2, 0));
+        assert_eq!(flat.get(&Point2::new(2, 0)), Some('c'));
+
+        b2.insert(Point2::new(0, 1), 'd');
+
+        flat_port.0.update();
+        assert_eq!(flat.area().range(), Point2::new(0, 0)..=Point2::new(2, 1));
+        assert_eq!(flat.get(&Point2::new(2, 1)), Some('d'));
+    }
+}
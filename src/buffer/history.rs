@@ -0,0 +1,458 @@
+use {
+    crate::{
+        buffer::{index_hashmap::IndexBuffer, singleton::SingletonBuffer, vec::{VecBuffer, VecDiff}},
+        view::{
+            Observer, OuterViewPort, View,
+            port::UpdateTask,
+            index::{IndexArea, IndexView},
+            singleton::SingletonView,
+            transaction,
+        },
+    },
+    std::sync::RwLock,
+    std::{
+        hash::Hash,
+        sync::{Arc, Weak},
+        time::{Duration, Instant},
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Receives the modifications which revert an edit of a buffer
+pub trait EditRecorder<I>: Send + Sync {
+    /// `inverse` reverts the edit in order of application,
+    /// `time` is when the edit was made
+    fn record(&mut self, inverse: Vec<I>, time: Instant);
+}
+
+type RecorderList<I> = Vec<Weak<RwLock<dyn EditRecorder<I>>>>;
+
+/// Recorders attached to a buffer.
+///
+/// The buffer passes the inverse of every edit right before applying it,
+/// so the overwritten values are known even if the edit is never
+/// delivered on its own port, e.g. when several edits share one update.
+pub struct EditHooks<I>(Arc<RwLock<RecorderList<I>>>);
+
+impl<I> Clone for EditHooks<I> {
+    fn clone(&self) -> Self {
+        EditHooks(self.0.clone())
+    }
+}
+
+impl<I> Default for EditHooks<I> {
+    fn default() -> Self {
+        EditHooks(Arc::new(RwLock::new(Vec::new())))
+    }
+}
+
+impl<I: Clone + Send + Sync + 'static> EditHooks<I> {
+    pub fn add(&self, recorder: Weak<RwLock<dyn EditRecorder<I>>>) {
+        self.0.write().unwrap().push(recorder);
+    }
+
+    /// `inverse` is only evaluated if a recorder is attached.
+    /// Inside of a transaction the edit is held back until it is committed,
+    /// so aborted edits are not recorded.
+    pub(crate) fn record(&self, inverse: impl FnOnce() -> Vec<I>) {
+        let recorders: Vec<_> = {
+            let mut hooks = self.0.write().unwrap();
+            hooks.retain(|r| r.strong_count() > 0);
+            hooks.iter().filter_map(|r| r.upgrade()).collect()
+        };
+        if recorders.is_empty() {
+            return;
+        }
+
+        let inverse = inverse();
+        let time = Instant::now();
+        let deliver = move || {
+            for recorder in recorders {
+                recorder.write().unwrap().record(inverse.clone(), time);
+            }
+        };
+
+        if transaction::is_open() {
+            transaction::defer(deliver);
+        } else {
+            deliver();
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Buffer whose modifications can be recorded and reverted
+pub trait Recordable: Clone + Send + Sync + 'static {
+    type Msg: Clone + Send + Sync;
+    type View: View<Msg = Self::Msg> + ?Sized + 'static;
+
+    /// single modification which reverts a recorded one
+    type Inverse: Clone + Send + Sync + 'static;
+
+    /// port whose updates delimit `Coalescing::Update` steps
+    fn recorded_port(&self) -> OuterViewPort<Self::View>;
+
+    fn edit_hooks(&self) -> &EditHooks<Self::Inverse>;
+
+    fn revert(&mut self, edit: Self::Inverse);
+}
+
+impl<T: Clone + Send + Sync + 'static> Recordable for VecBuffer<T> {
+    type Msg = VecDiff<T>;
+    type View = RwLock<Vec<T>>;
+    type Inverse = VecDiff<T>;
+
+    fn recorded_port(&self) -> OuterViewPort<RwLock<Vec<T>>> {
+        self.get_port()
+    }
+
+    fn edit_hooks(&self) -> &EditHooks<VecDiff<T>> {
+        &self.hooks
+    }
+
+    fn revert(&mut self, edit: VecDiff<T>) {
+        self.apply_diff(edit);
+    }
+}
+
+impl<Key, Item> Recordable for IndexBuffer<Key, Item>
+where
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    Item: Clone + Send + Sync + 'static,
+{
+    type Msg = IndexArea<Key>;
+    type View = dyn IndexView<Key, Item = Item>;
+    type Inverse = (Key, Option<Item>);
+
+    fn recorded_port(&self) -> OuterViewPort<dyn IndexView<Key, Item = Item>> {
+        self.get_port()
+    }
+
+    fn edit_hooks(&self) -> &EditHooks<(Key, Option<Item>)> {
+        &self.hooks
+    }
+
+    fn revert(&mut self, (key, val): (Key, Option<Item>)) {
+        self.update(key, val);
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Recordable for SingletonBuffer<T> {
+    type Msg = ();
+    type View = dyn SingletonView<Item = T>;
+    type Inverse = T;
+
+    fn recorded_port(&self) -> OuterViewPort<dyn SingletonView<Item = T>> {
+        self.get_port()
+    }
+
+    fn edit_hooks(&self) -> &EditHooks<T> {
+        &self.hooks
+    }
+
+    fn revert(&mut self, val: T) {
+        self.set(val);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Which recorded modifications are undone together
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coalescing {
+    /// every modification is a separate undo step
+    None,
+
+    /// all modifications delivered with one update are one undo step,
+    /// e.g. those of a committed `Transaction`
+    Update,
+
+    /// modifications made within the duration after each other are one undo step,
+    /// timed when they are applied to the buffer rather than when they are delivered
+    Within(Duration),
+
+    /// modifications are grouped until `History::checkpoint()`
+    Manual,
+}
+
+/// reverting modifications of each recorded edit
+type Step<I> = Vec<Vec<I>>;
+
+pub struct HistoryObserver<B: Recordable> {
+    coalescing: Coalescing,
+
+    undo: Vec<Step<B::Inverse>>,
+    redo: Vec<Step<B::Inverse>>,
+
+    /// step which is still being recorded
+    open: Step<B::Inverse>,
+    last_edit: Option<Instant>,
+
+    /// set while a step is undone or redone,
+    /// the recorded modifications then form the opposite step
+    replaying: bool,
+
+    can_undo: SingletonBuffer<bool>,
+    can_redo: SingletonBuffer<bool>,
+}
+
+impl<B: Recordable> HistoryObserver<B> {
+    fn close_step(&mut self) {
+        if !self.open.is_empty() {
+            let step = std::mem::take(&mut self.open);
+            self.undo.push(step);
+        }
+    }
+
+    fn update_flags(&mut self) {
+        self.can_undo.set_if_changed(!self.undo.is_empty() || !self.open.is_empty());
+        self.can_redo.set_if_changed(!self.redo.is_empty());
+    }
+}
+
+impl<B: Recordable> EditRecorder<B::Inverse> for HistoryObserver<B> {
+    fn record(&mut self, inverse: Vec<B::Inverse>, time: Instant) {
+        if self.replaying {
+            self.open.push(inverse);
+            return;
+        }
+
+        match self.coalescing {
+            Coalescing::None => self.close_step(),
+            Coalescing::Within(d) => {
+                if self.last_edit.is_some_and(|t| time.saturating_duration_since(t) > d) {
+                    self.close_step();
+                }
+            }
+            Coalescing::Update | Coalescing::Manual => {}
+        }
+
+        self.last_edit = Some(time);
+        self.open.push(inverse);
+        self.redo.clear();
+        self.update_flags();
+    }
+}
+
+/// only observes the port to know where updates end
+impl<B: Recordable> Observer<B::View> for HistoryObserver<B> {
+    fn notify(&mut self, _msg: &B::Msg) {}
+
+    fn end_update(&mut self) {
+        if self.coalescing == Coalescing::Update && !self.replaying {
+            self.close_step();
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Undo/redo history of a buffer.
+///
+/// Records the inverse of every modification of the buffer
+/// and groups them into undo steps as configured by `Coalescing`.
+pub struct History<B: Recordable> {
+    buffer: B,
+    port: OuterViewPort<B::View>,
+    state: Arc<RwLock<HistoryObserver<B>>>,
+}
+
+impl<B: Recordable> History<B> {
+    pub fn new(buffer: B, coalescing: Coalescing) -> Self {
+        let port = buffer.recorded_port();
+        let state = Arc::new(RwLock::new(HistoryObserver {
+            coalescing,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            open: Vec::new(),
+            last_edit: None,
+            replaying: false,
+            can_undo: SingletonBuffer::new(false),
+            can_redo: SingletonBuffer::new(false),
+        }));
+
+        port.add_observer(state.clone());
+        buffer.edit_hooks().add(Arc::downgrade(&state) as Weak<RwLock<dyn EditRecorder<B::Inverse>>>);
+        History { buffer, port, state }
+    }
+
+    /// finish the current undo step
+    pub fn checkpoint(&self) {
+        self.port.0.update();
+        self.state.write().unwrap().close_step();
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.checkpoint();
+        let step = self.state.write().unwrap().undo.pop();
+        if let Some(step) = step {
+            let redo_step = self.replay(step);
+            let mut state = self.state.write().unwrap();
+            state.redo.push(redo_step);
+            state.update_flags();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.port.0.update();
+        let step = self.state.write().unwrap().redo.pop();
+        if let Some(step) = step {
+            let undo_step = self.replay(step);
+            let mut state = self.state.write().unwrap();
+            state.undo.push(undo_step);
+            state.update_flags();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// apply the modifications of `step` to the buffer
+    /// and return the step which reverts them again
+    fn replay(&mut self, step: Step<B::Inverse>) -> Step<B::Inverse> {
+        self.state.write().unwrap().replaying = true;
+        for edits in step.into_iter().rev() {
+            for edit in edits {
+                self.buffer.revert(edit);
+            }
+        }
+        self.port.0.update();
+
+        let mut state = self.state.write().unwrap();
+        state.replaying = false;
+        std::mem::take(&mut state.open)
+    }
+
+    pub fn can_undo(&self) -> OuterViewPort<dyn SingletonView<Item = bool>> {
+        self.state.read().unwrap().can_undo.get_port()
+    }
+
+    pub fn can_redo(&self) -> OuterViewPort<dyn SingletonView<Item = bool>> {
+        self.state.read().unwrap().can_redo.get_port()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::{history::*, index_hashmap::*, singleton::*, vec::*},
+        view::{Transaction, index::IndexView, singleton::SingletonView},
+    };
+
+    fn contents(buffer: &VecBuffer<char>) -> String {
+        buffer.get_port().get_view().unwrap().read().unwrap().iter().collect()
+    }
+
+    #[test]
+    fn history_vec() {
+        let mut buffer = VecBuffer::with_data(vec!['a', 'b']);
+        let mut history = History::new(buffer.clone(), Coalescing::Update);
+        let can_undo = history.can_undo().get_view().unwrap();
+        let can_redo = history.can_redo().get_view().unwrap();
+        assert!(!can_undo.get());
+
+        buffer.push('c');
+        history.checkpoint();
+
+        let tx = Transaction::begin();
+        buffer.remove(0);
+        buffer.update(0, 'B');
+        buffer.insert(0, 'x');
+        tx.commit();
+        assert_eq!(contents(&buffer), "xBc");
+
+        assert!(history.undo());
+        assert_eq!(contents(&buffer), "abc");
+        assert!(can_undo.get());
+        assert!(can_redo.get());
+
+        assert!(history.undo());
+        assert_eq!(contents(&buffer), "ab");
+        assert!(!can_undo.get());
+        assert!(!history.undo());
+
+        assert!(history.redo());
+        assert!(history.redo());
+        assert_eq!(contents(&buffer), "xBc");
+        assert!(!can_redo.get());
+
+        buffer.clear();
+        history.undo();
+        assert_eq!(contents(&buffer), "xBc");
+
+        // a new modification discards the redo steps
+        buffer.push('d');
+        history.checkpoint();
+        assert!(!can_redo.get());
+        assert!(!history.redo());
+    }
+
+    #[test]
+    fn history_index_singleton() {
+        let mut index = IndexBuffer::<u8, char>::new();
+        index.insert(1, 'a');
+        let mut index_history = History::new(index.clone(), Coalescing::None);
+
+        index.insert(1, 'b');
+        index.insert(2, 'c');
+        index_history.undo();
+        assert_eq!(index.get_port().get_view().unwrap().get(&2), None);
+        index_history.undo();
+        assert_eq!(index.get(&1), Some('a'));
+
+        let mut value = SingletonBuffer::new(0);
+        let mut value_history = History::new(value.clone(), Coalescing::Manual);
+        value.set(1);
+        value.set(2);
+        value_history.checkpoint();
+        value.set(3);
+
+        value_history.undo();
+        assert_eq!(value.get(), 2);
+        value_history.undo();
+        assert_eq!(value.get(), 0);
+        value_history.redo();
+        assert_eq!(value.get(), 2);
+    }
+
+    #[test]
+    fn history_edits_per_update() {
+        // several edits before the port is updated are still separate steps
+        let mut value = SingletonBuffer::new(0);
+        let mut value_history = History::new(value.clone(), Coalescing::None);
+        value.set(1);
+        value.set(2);
+        value_history.undo();
+        assert_eq!(value.get(), 1);
+        value_history.undo();
+        assert_eq!(value.get(), 0);
+
+        let mut index = IndexBuffer::<u8, char>::new();
+        let mut index_history = History::new(index.clone(), Coalescing::None);
+        index.insert(1, 'a');
+        index.insert(1, 'b');
+        index.remove(1);
+        index_history.undo();
+        assert_eq!(index.get(&1), Some('b'));
+        index_history.undo();
+        assert_eq!(index.get(&1), Some('a'));
+        index_history.undo();
+        assert_eq!(index.get(&1), None);
+
+        // aborted edits are not recorded
+        let tx = Transaction::begin();
+        value.set(5);
+        tx.abort();
+        value.set(3);
+        value_history.undo();
+        assert_eq!(value.get(), 0);
+        assert!(!value_history.undo());
+    }
+}
@@ -1,8 +1,10 @@
 use {
     crate::{
+        buffer::history::EditHooks,
         view::{
             InnerViewPort, OuterViewPort, ViewPort, View,
             index::{IndexArea, IndexView},
+            transaction,
         },
     },
     std::sync::RwLock,
@@ -46,6 +48,7 @@ where
 {
     data: Arc<RwLock<HashMap<Key, Item>>>,
     port: InnerViewPort<dyn IndexView<Key, Item = Item>>,
+    pub(crate) hooks: EditHooks<(Key, Option<Item>)>,
 }
 
 impl<Key, Item> FromIterator<(Key, Item)> for IndexBuffer<Key, Item>
@@ -71,7 +74,8 @@ where
 
         IndexBuffer {
             data,
-            port
+            port,
+            hooks: EditHooks::default()
         }
     }
 
@@ -96,6 +100,8 @@ where
     }
 
     pub fn update(&mut self, key: Key, item: Option<Item>) {
+        self.hooks.record(|| vec![(key.clone(), self.get(&key))]);
+        transaction::save(&self.data);
         if let Some(item) = item {
             self.data.write().unwrap().insert(key.clone(), item);
         } else {
@@ -105,6 +111,8 @@ where
     }
     
     pub fn insert(&mut self, key: Key, item: Item) {
+        self.hooks.record(|| vec![(key.clone(), self.get(&key))]);
+        transaction::save(&self.data);
         self.data.write().unwrap().insert(key.clone(), item);
         self.port.notify(&IndexArea::Set(vec![key]));
     }
@@ -119,11 +127,17 @@ where
     }
 
     pub fn remove(&mut self, key: Key) {
+        self.hooks.record(|| vec![(key.clone(), self.get(&key))]);
+        transaction::save(&self.data);
         self.data.write().unwrap().remove(&key);
         self.port.notify(&IndexArea::Set(vec![key]));
     }
 
     pub fn clear(&mut self) {
+        self.hooks.record(|| {
+            self.data.read().unwrap().iter().map(|(key, item)| (key.clone(), Some(item.clone()))).collect()
+        });
+        transaction::save(&self.data);
         self.data.write().unwrap().clear();
         self.port.notify(&IndexArea::Set(self.data.read().unwrap().keys().cloned().collect()));
     }
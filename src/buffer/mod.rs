@@ -2,4 +2,6 @@
 pub mod singleton;
 pub mod vec;
 pub mod index_hashmap;
+pub mod grid_hashmap;
+pub mod history;
 
@@ -1,9 +1,11 @@
 use {
     crate::{
+        buffer::history::EditHooks,
         view::{
             Observer,
             InnerViewPort, OuterViewPort, View, ViewPort,
-            singleton::SingletonView
+            singleton::SingletonView,
+            transaction,
         },
     },
     std::sync::RwLock,
@@ -43,7 +45,8 @@ where
     T: Clone + Send + Sync + 'static
 {
     pub value: Arc<RwLock<T>>,
-    pub port: InnerViewPort<dyn SingletonView<Item = T>>
+    pub port: InnerViewPort<dyn SingletonView<Item = T>>,
+    pub(crate) hooks: EditHooks<T>
 }
 
 pub struct SingletonBufferTarget<T>
@@ -80,7 +83,8 @@ where
 
         SingletonBuffer {
             value,
-            port
+            port,
+            hooks: EditHooks::default()
         }
     }
 
@@ -124,6 +128,8 @@ where
     }
 
     pub fn set(&mut self, new_value: T) {
+        self.hooks.record(|| vec![self.get()]);
+        transaction::save(&self.value);
         let mut v = self.value.write().unwrap();
         *v = new_value;
         drop(v);
@@ -135,6 +141,23 @@ where
     }
 }
 
+impl<T> SingletonBuffer<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static
+{
+    /// like `set`, but only notifies if the value actually changed
+    pub fn set_if_changed(&mut self, new_value: T) -> bool {
+        if *self.value.read().unwrap() == new_value {
+            return false;
+        }
+        self.hooks.record(|| vec![self.get()]);
+        transaction::save(&self.value);
+        *self.value.write().unwrap() = new_value;
+        self.port.notify(&());
+        true
+    }
+}
+
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
 pub struct MutableSingletonAccess<T>
@@ -1,8 +1,10 @@
 use {
     crate::{
+        buffer::history::EditHooks,
         view::{
-            InnerViewPort, OuterViewPort, View, Observer, ViewPort,
-            list::*
+            InnerViewPort, OuterViewPort, View, ViewSnapshot, Observer, ViewPort,
+            list::*,
+            transaction,
         }
     },
     std::sync::RwLock,
@@ -32,6 +34,19 @@ where
     type Msg = VecDiff<T>;
 }
 
+impl<T> ViewSnapshot for RwLock<Vec<T>>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn snapshot(_old: Option<Arc<Self>>, new: Option<Arc<Self>>) -> Vec<VecDiff<T>> {
+        let mut diffs = vec![ VecDiff::Clear ];
+        if let Some(data) = new.as_ref() {
+            diffs.extend(data.read().unwrap().iter().cloned().map(VecDiff::Push));
+        }
+        diffs
+    }
+}
+
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
 pub struct VecBufferTarget<T>
@@ -76,7 +91,8 @@ where
     T: Clone + Send + Sync + 'static,
 {
     data: Arc<RwLock<Vec<T>>>,
-    port: InnerViewPort<RwLock<Vec<T>>>
+    port: InnerViewPort<RwLock<Vec<T>>>,
+    pub(crate) hooks: EditHooks<VecDiff<T>>
 }
 
 impl<T> VecBuffer<T>
@@ -93,7 +109,8 @@ where
             
         VecBuffer {
             data,
-            port
+            port,
+            hooks: EditHooks::default()
         }
     }
 
@@ -128,6 +145,17 @@ where
     }
 
     pub fn apply_diff(&mut self, diff: VecDiff<T>) {
+        self.hooks.record(|| {
+            let data = self.data.read().unwrap();
+            match &diff {
+                VecDiff::Clear => data.iter().cloned().map(VecDiff::Push).collect(),
+                VecDiff::Push(_) => vec![VecDiff::Remove(data.len())],
+                VecDiff::Remove(idx) => vec![VecDiff::Insert { idx: *idx, val: data[*idx].clone() }],
+                VecDiff::Insert { idx, .. } => vec![VecDiff::Remove(*idx)],
+                VecDiff::Update { idx, .. } => vec![VecDiff::Update { idx: *idx, val: data[*idx].clone() }],
+            }
+        });
+        transaction::save(&self.data);
         let mut data = self.data.write().unwrap();
         match &diff {
             VecDiff::Clear => {
@@ -0,0 +1,145 @@
+use {
+    crate::{
+        buffer::index_hashmap::IndexBuffer,
+        lens::{Lens, MapLens},
+        view::{OuterViewPort, port::UpdateTask, index::IndexView},
+    },
+    std::{hash::Hash, sync::Arc},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Index view which can be written to
+pub trait IndexWriter<Key, T>: Send + Sync
+where
+    Key: Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    fn read_port(&self) -> OuterViewPort<dyn IndexView<Key, Item = T>>;
+
+    fn insert(&mut self, key: Key, val: T);
+    fn remove(&mut self, key: Key);
+
+    fn lens<U, L>(self, lens: L) -> LensIndex<Key, T, U, Self>
+    where
+        Self: Sized,
+        U: Clone + Send + Sync + 'static,
+        L: Lens<T, U> + 'static,
+    {
+        LensIndex::new(self, Arc::new(lens))
+    }
+
+    fn map_bidirectional<U, F, G>(self, f: F, f_inv: G) -> LensIndex<Key, T, U, Self>
+    where
+        Self: Sized,
+        U: Clone + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+        G: Fn(&U) -> T + Send + Sync + 'static,
+    {
+        self.lens(MapLens::new(f, f_inv))
+    }
+}
+
+impl<Key, T> IndexWriter<Key, T> for IndexBuffer<Key, T>
+where
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    fn read_port(&self) -> OuterViewPort<dyn IndexView<Key, Item = T>> {
+        self.get_port()
+    }
+
+    fn insert(&mut self, key: Key, val: T) {
+        IndexBuffer::insert(self, key, val);
+    }
+
+    fn remove(&mut self, key: Key) {
+        IndexBuffer::remove(self, key);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Projects every item through a lens,
+/// written items are put back under the same key
+pub struct LensIndex<Key, T, U, W>
+where
+    Key: Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: IndexWriter<Key, T>,
+{
+    src: W,
+    src_port: OuterViewPort<dyn IndexView<Key, Item = T>>,
+    port: OuterViewPort<dyn IndexView<Key, Item = U>>,
+    lens: Arc<dyn Lens<T, U>>,
+}
+
+impl<Key, T, U, W> LensIndex<Key, T, U, W>
+where
+    Key: Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: IndexWriter<Key, T>,
+{
+    pub fn new(src: W, lens: Arc<dyn Lens<T, U>>) -> Self {
+        let src_port = src.read_port();
+        let port = src_port.map_item({
+            let lens = lens.clone();
+            move |_key, x| lens.get(x)
+        });
+
+        LensIndex { src, src_port, port, lens }
+    }
+}
+
+impl<Key, T, U, W> IndexWriter<Key, U> for LensIndex<Key, T, U, W>
+where
+    Key: Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: IndexWriter<Key, T>,
+{
+    fn read_port(&self) -> OuterViewPort<dyn IndexView<Key, Item = U>> {
+        self.port.clone()
+    }
+
+    fn insert(&mut self, key: Key, val: U) {
+        self.src_port.0.update();
+        let old = self.src_port.get_view().and_then(|v| v.get(&key));
+        let new = self.lens.put(old.as_ref(), val);
+        self.src.insert(key, new);
+    }
+
+    fn remove(&mut self, key: Key) {
+        self.src.remove(key);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::index_hashmap::*,
+        lens::IndexWriter,
+        view::index::IndexView,
+    };
+
+    #[test]
+    fn lens_index() {
+        let buffer = IndexBuffer::<char, i32>::new();
+
+        let mut writer = buffer.clone().map_bidirectional(|x| x * 10, |y| y / 10);
+        let view = writer.read_port().get_view().unwrap();
+
+        writer.insert('a', 10);
+        writer.insert('b', 20);
+        assert_eq!(buffer.get(&'a'), Some(1));
+        assert_eq!(view.get(&'b'), Some(20));
+
+        writer.remove('a');
+        assert_eq!(buffer.get(&'a'), None);
+        assert_eq!(view.get(&'a'), None);
+    }
+}
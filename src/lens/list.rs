@@ -0,0 +1,320 @@
+use {
+    crate::{
+        buffer::vec::VecBuffer,
+        lens::{Lens, MapLens},
+        projection::filter_list::FilterListView,
+        view::{
+            OuterViewPort,
+            port::UpdateTask,
+            list::ListView,
+        },
+    },
+    std::sync::Arc,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// List view which can be written to
+pub trait ListWriter<T: Clone + Send + Sync + 'static>: Send + Sync {
+    fn read_port(&self) -> OuterViewPort<dyn ListView<T>>;
+
+    fn insert(&mut self, idx: usize, val: T);
+    fn remove(&mut self, idx: usize);
+    fn update(&mut self, idx: usize, val: T);
+
+    fn lens<U, L>(self, lens: L) -> LensList<T, U, Self>
+    where
+        Self: Sized,
+        U: Clone + Send + Sync + 'static,
+        L: Lens<T, U> + 'static,
+    {
+        LensList::new(self, Arc::new(lens))
+    }
+
+    fn map_bidirectional<U, F, G>(self, f: F, f_inv: G) -> LensList<T, U, Self>
+    where
+        Self: Sized,
+        U: Clone + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+        G: Fn(&U) -> T + Send + Sync + 'static,
+    {
+        self.lens(MapLens::new(f, f_inv))
+    }
+
+    fn filter<P>(self, pred: P) -> FilterListWriter<T, Self>
+    where
+        Self: Sized,
+        P: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        FilterListWriter::new(self, Arc::new(pred))
+    }
+
+    fn enumerate(self) -> EnumerateListWriter<T, Self>
+    where
+        Self: Sized,
+    {
+        EnumerateListWriter::new(self)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> ListWriter<T> for VecBuffer<T> {
+    fn read_port(&self) -> OuterViewPort<dyn ListView<T>> {
+        self.get_port().to_list()
+    }
+
+    fn insert(&mut self, idx: usize, val: T) {
+        VecBuffer::insert(self, idx, val);
+    }
+
+    fn remove(&mut self, idx: usize) {
+        VecBuffer::remove(self, idx);
+    }
+
+    fn update(&mut self, idx: usize, val: T) {
+        VecBuffer::update(self, idx, val);
+    }
+}
+
+/// view of the source, brought up to date with all previous writes
+fn current_view<T: Clone + Send + Sync + 'static>(
+    port: &OuterViewPort<dyn ListView<T>>,
+) -> Option<Arc<dyn ListView<T>>> {
+    port.0.update();
+    port.get_view()
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Projects every item through a lens,
+/// written items are put back at the same index
+pub struct LensList<T, U, W>
+where
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    src: W,
+    src_port: OuterViewPort<dyn ListView<T>>,
+    port: OuterViewPort<dyn ListView<U>>,
+    lens: Arc<dyn Lens<T, U>>,
+}
+
+impl<T, U, W> LensList<T, U, W>
+where
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    pub fn new(src: W, lens: Arc<dyn Lens<T, U>>) -> Self {
+        let src_port = src.read_port();
+        let port = src_port.map({
+            let lens = lens.clone();
+            move |x| lens.get(x)
+        });
+
+        LensList { src, src_port, port, lens }
+    }
+}
+
+impl<T, U, W> ListWriter<U> for LensList<T, U, W>
+where
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    fn read_port(&self) -> OuterViewPort<dyn ListView<U>> {
+        self.port.clone()
+    }
+
+    fn insert(&mut self, idx: usize, val: U) {
+        let new = self.lens.put(None, val);
+        self.src.insert(idx, new);
+    }
+
+    fn remove(&mut self, idx: usize) {
+        self.src.remove(idx);
+    }
+
+    fn update(&mut self, idx: usize, val: U) {
+        let old = current_view(&self.src_port).and_then(|v| v.get(&idx));
+        let new = self.lens.put(old.as_ref(), val);
+        self.src.update(idx, new);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+type FilterView<T> = FilterListView<T, Box<dyn Fn(&T) -> bool + Send + Sync>>;
+
+/// Writes on the filtered position `idx` go to the source index
+/// of the `idx`-th item which satisfies the predicate.
+/// Items inserted at the end of the filtered list are appended to the source.
+/// Values which do not satisfy the predicate are not written,
+/// since they would not show up at the written position.
+pub struct FilterListWriter<T, W>
+where
+    T: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    src: W,
+    src_port: OuterViewPort<dyn ListView<T>>,
+    port: OuterViewPort<dyn ListView<T>>,
+    filter: FilterView<T>,
+    pred: Arc<dyn Fn(&T) -> bool + Send + Sync>,
+}
+
+impl<T, W> FilterListWriter<T, W>
+where
+    T: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    pub fn new(src: W, pred: Arc<dyn Fn(&T) -> bool + Send + Sync>) -> Self {
+        let src_port = src.read_port();
+        let (port, filter) = src_port.filter_list(Box::new({
+            let pred = pred.clone();
+            move |x: &T| pred(x)
+        }) as Box<dyn Fn(&T) -> bool + Send + Sync>);
+
+        FilterListWriter { src, src_port, port, filter, pred }
+    }
+
+    /// source index of the filtered position `idx`
+    fn select(&self, idx: usize) -> Option<usize> {
+        self.port.0.update();
+        self.filter.read().unwrap().select(idx)
+    }
+}
+
+impl<T, W> ListWriter<T> for FilterListWriter<T, W>
+where
+    T: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    fn read_port(&self) -> OuterViewPort<dyn ListView<T>> {
+        self.port.clone()
+    }
+
+    fn insert(&mut self, idx: usize, val: T) {
+        if !(self.pred)(&val) {
+            return;
+        }
+        let src_idx = match self.select(idx) {
+            Some(i) => i,
+            None => current_view(&self.src_port).and_then(|v| v.len()).unwrap_or(0),
+        };
+        self.src.insert(src_idx, val);
+    }
+
+    fn remove(&mut self, idx: usize) {
+        if let Some(src_idx) = self.select(idx) {
+            self.src.remove(src_idx);
+        }
+    }
+
+    fn update(&mut self, idx: usize, val: T) {
+        if !(self.pred)(&val) {
+            return;
+        }
+        if let Some(src_idx) = self.select(idx) {
+            self.src.update(src_idx, val);
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Pairs every item with its position,
+/// the position of written items is ignored
+pub struct EnumerateListWriter<T, W>
+where
+    T: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    src: W,
+    port: OuterViewPort<dyn ListView<(usize, T)>>,
+}
+
+impl<T, W> EnumerateListWriter<T, W>
+where
+    T: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    pub fn new(src: W) -> Self {
+        let port = src.read_port().enumerate();
+        EnumerateListWriter { src, port }
+    }
+}
+
+impl<T, W> ListWriter<(usize, T)> for EnumerateListWriter<T, W>
+where
+    T: Clone + Send + Sync + 'static,
+    W: ListWriter<T>,
+{
+    fn read_port(&self) -> OuterViewPort<dyn ListView<(usize, T)>> {
+        self.port.clone()
+    }
+
+    fn insert(&mut self, idx: usize, (_, val): (usize, T)) {
+        self.src.insert(idx, val);
+    }
+
+    fn remove(&mut self, idx: usize) {
+        self.src.remove(idx);
+    }
+
+    fn update(&mut self, idx: usize, (_, val): (usize, T)) {
+        self.src.update(idx, val);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::vec::*,
+        lens::ListWriter,
+        view::{port::UpdateTask, list::ListViewExt},
+    };
+
+    #[test]
+    fn lens_list() {
+        let buffer = VecBuffer::with_data(vec![1, 2, 3, 4]);
+
+        // edit the even items as strings
+        let mut writer = buffer.clone()
+            .map_bidirectional(|x: &i32| x.to_string(), |s: &String| s.parse().unwrap())
+            .filter(|s| s.parse::<i32>().unwrap() % 2 == 0);
+
+        let port = writer.read_port();
+        let check = |expected: Vec<&str>| {
+            port.0.update();
+            assert_eq!(port.get_view().unwrap().iter().collect::<Vec<_>>(), expected);
+        };
+        check(vec!["2", "4"]);
+
+        writer.update(1, "40".into());
+        writer.insert(1, "6".into());
+        writer.remove(0);
+        check(vec!["6", "40"]);
+        assert_eq!(*buffer.get_port().get_view().unwrap().read().unwrap(), vec![1, 3, 6, 40]);
+
+        writer.insert(2, "8".into());
+        check(vec!["6", "40", "8"]);
+        assert_eq!(*buffer.get_port().get_view().unwrap().read().unwrap(), vec![1, 3, 6, 40, 8]);
+
+        // values which do not satisfy the predicate are not written
+        writer.insert(0, "5".into());
+        writer.update(1, "7".into());
+        check(vec!["6", "40", "8"]);
+        assert_eq!(*buffer.get_port().get_view().unwrap().read().unwrap(), vec![1, 3, 6, 40, 8]);
+
+        let mut numbered = buffer.clone().enumerate();
+        numbered.update(2, (0, 30));
+        numbered.read_port().0.update();
+        assert_eq!(
+            numbered.read_port().get_view().unwrap().iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 3), (2, 30), (3, 40), (4, 8)]
+        );
+    }
+}
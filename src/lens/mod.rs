@@ -0,0 +1,53 @@
+//! Write paths through projections.
+//!
+//! A *Writer* pairs the port of a (projected) view with the buffer behind it,
+//! so that edits on the projected view are translated back into edits of the source.
+
+pub mod singleton;
+pub mod list;
+pub mod index;
+
+pub use {
+    singleton::SingletonWriter,
+    list::ListWriter,
+    index::IndexWriter,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Two-way mapping between a source value `A` and its projection `B`.
+pub trait Lens<A, B>: Send + Sync {
+    fn get(&self, src: &A) -> B;
+
+    /// source value which projects to `val`,
+    /// `src` is the source value it replaces (`None` on insertion)
+    fn put(&self, src: Option<&A>, val: B) -> A;
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Lens made of a function and its inverse
+pub struct MapLens<F, G> {
+    f: F,
+    f_inv: G,
+}
+
+impl<F, G> MapLens<F, G> {
+    pub fn new(f: F, f_inv: G) -> Self {
+        MapLens { f, f_inv }
+    }
+}
+
+impl<A, B, F, G> Lens<A, B> for MapLens<F, G>
+where
+    F: Fn(&A) -> B + Send + Sync,
+    G: Fn(&B) -> A + Send + Sync,
+{
+    fn get(&self, src: &A) -> B {
+        (self.f)(src)
+    }
+
+    fn put(&self, _src: Option<&A>, val: B) -> A {
+        (self.f_inv)(&val)
+    }
+}
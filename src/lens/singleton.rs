@@ -0,0 +1,138 @@
+use {
+    crate::{
+        buffer::singleton::SingletonBuffer,
+        lens::{Lens, MapLens},
+        view::{OuterViewPort, singleton::SingletonView},
+    },
+    std::sync::Arc,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Singleton view which can be written to
+pub trait SingletonWriter<T: Clone + Send + Sync + 'static>: Send + Sync {
+    fn read_port(&self) -> OuterViewPort<dyn SingletonView<Item = T>>;
+    fn set(&mut self, val: T);
+
+    fn lens<U, L>(self, lens: L) -> LensSingleton<T, U, Self>
+    where
+        Self: Sized,
+        U: Clone + Send + Sync + 'static,
+        L: Lens<T, U> + 'static,
+    {
+        LensSingleton::new(self, Arc::new(lens))
+    }
+
+    fn map_bidirectional<U, F, G>(self, f: F, f_inv: G) -> LensSingleton<T, U, Self>
+    where
+        Self: Sized,
+        U: Clone + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+        G: Fn(&U) -> T + Send + Sync + 'static,
+    {
+        self.lens(MapLens::new(f, f_inv))
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> SingletonWriter<T> for SingletonBuffer<T> {
+    fn read_port(&self) -> OuterViewPort<dyn SingletonView<Item = T>> {
+        self.get_port()
+    }
+
+    fn set(&mut self, val: T) {
+        SingletonBuffer::set(self, val);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Projects the source through a lens,
+/// written values are put back into the source
+pub struct LensSingleton<T, U, W>
+where
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: SingletonWriter<T>,
+{
+    src: W,
+    src_port: OuterViewPort<dyn SingletonView<Item = T>>,
+    port: OuterViewPort<dyn SingletonView<Item = U>>,
+    lens: Arc<dyn Lens<T, U>>,
+}
+
+impl<T, U, W> LensSingleton<T, U, W>
+where
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: SingletonWriter<T>,
+{
+    pub fn new(src: W, lens: Arc<dyn Lens<T, U>>) -> Self {
+        let src_port = src.read_port();
+        let port = src_port.map({
+            let lens = lens.clone();
+            move |x| lens.get(&x)
+        });
+
+        LensSingleton { src, src_port, port, lens }
+    }
+}
+
+impl<T, U, W> SingletonWriter<U> for LensSingleton<T, U, W>
+where
+    T: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+    W: SingletonWriter<T>,
+{
+    fn read_port(&self) -> OuterViewPort<dyn SingletonView<Item = U>> {
+        self.port.clone()
+    }
+
+    fn set(&mut self, val: U) {
+        let old = self.src_port.get_view().map(|v| v.get());
+        let new = self.lens.put(old.as_ref(), val);
+        self.src.set(new);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::singleton::*,
+        lens::{Lens, SingletonWriter},
+        view::{port::UpdateTask, singleton::SingletonView},
+    };
+
+    /// first component of a pair
+    struct First;
+
+    impl Lens<(char, u32), char> for First {
+        fn get(&self, src: &(char, u32)) -> char {
+            src.0
+        }
+
+        fn put(&self, src: Option<&(char, u32)>, val: char) -> (char, u32) {
+            (val, src.map(|s| s.1).unwrap_or(0))
+        }
+    }
+
+    #[test]
+    fn lens_singleton() {
+        let buffer = SingletonBuffer::new(('a', 7));
+
+        let mut first = buffer.clone().lens(First);
+        let first_view = first.read_port().get_view().unwrap();
+        assert_eq!(first_view.get(), 'a');
+
+        first.set('b');
+        first.read_port().0.update();
+        assert_eq!(buffer.get(), ('b', 7));
+        assert_eq!(first_view.get(), 'b');
+
+        let mut count = buffer.clone().map_bidirectional(|x| x.1 * 2, |y| ('c', y / 2));
+        count.set(20);
+        assert_eq!(buffer.get(), ('c', 10));
+        assert_eq!(count.read_port().get_view().unwrap().get(), 20);
+    }
+}
@@ -1,4 +1,5 @@
 #![feature(trait_alias)]
+#![feature(min_specialization)]
 
 //! Rust Runtime for Reactive View-Projections
 //!
@@ -48,4 +49,7 @@
 pub mod view;
 pub mod buffer;
 pub mod projection;
+pub mod lens;
+pub mod testing;
+pub mod remote;
 
@@ -0,0 +1,258 @@
+use {
+    crate::{
+        view::{
+            InnerViewPort, Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            singleton::SingletonView,
+        },
+        projection::projection_helper::ProjectionHelper,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<A: 'static> OuterViewPort<dyn SingletonView<Item = A>> {
+    pub fn zip<B: 'static>(
+        &self,
+        other: &OuterViewPort<dyn SingletonView<Item = B>>,
+    ) -> OuterViewPort<dyn SingletonView<Item = (A, B)>> {
+        self.map2(other, |a, b| (a, b))
+    }
+
+    /// recomputed whenever one of both singletons notifies
+    pub fn map2<B, DstItem, F>(
+        &self,
+        other: &OuterViewPort<dyn SingletonView<Item = B>>,
+        f: F,
+    ) -> OuterViewPort<dyn SingletonView<Item = DstItem>>
+    where
+        B: 'static,
+        DstItem: 'static,
+        F: Fn(A, B) -> DstItem + Send + Sync + 'static,
+    {
+        let port = ViewPort::new();
+        Map2Singleton::new(self.clone(), other.clone(), f, port.inner());
+        port.into_outer()
+    }
+
+    pub fn map3<B, C, DstItem, F>(
+        &self,
+        b: &OuterViewPort<dyn SingletonView<Item = B>>,
+        c: &OuterViewPort<dyn SingletonView<Item = C>>,
+        f: F,
+    ) -> OuterViewPort<dyn SingletonView<Item = DstItem>>
+    where
+        B: 'static,
+        C: 'static,
+        DstItem: 'static,
+        F: Fn(A, B, C) -> DstItem + Send + Sync + 'static,
+    {
+        self.zip(b).map2(c, move |(a, b), c| f(a, b, c))
+    }
+}
+
+/// singleton computed from the values of all `ports`,
+/// recomputed whenever one of them notifies
+pub fn combine<Item, DstItem, F>(
+    ports: Vec<OuterViewPort<dyn SingletonView<Item = Item>>>,
+    f: F,
+) -> OuterViewPort<dyn SingletonView<Item = DstItem>>
+where
+    Item: 'static,
+    DstItem: 'static,
+    F: Fn(Vec<Item>) -> DstItem + Send + Sync + 'static,
+{
+    let port = ViewPort::new();
+    CombineSingleton::new(ports, f, port.inner());
+    port.into_outer()
+}
+
+fn get_arg<Item>(arg: &RwLock<Option<Arc<dyn SingletonView<Item = Item>>>>) -> Item {
+    arg.read().unwrap().as_ref().unwrap().get()
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+type SingletonArg<Item> = Arc<RwLock<Option<Arc<dyn SingletonView<Item = Item>>>>>;
+
+pub struct Map2Singleton<A, B, DstItem, F>
+where
+    A: 'static,
+    B: 'static,
+    DstItem: 'static,
+    F: Fn(A, B) -> DstItem + Send + Sync + 'static,
+{
+    a: SingletonArg<A>,
+    b: SingletonArg<B>,
+    f: F,
+    cast: Arc<RwLock<ObserverBroadcast<dyn SingletonView<Item = DstItem>>>>,
+    proj_helper: ProjectionHelper<usize, Self>,
+}
+
+impl<A, B, DstItem, F> Map2Singleton<A, B, DstItem, F>
+where
+    A: 'static,
+    B: 'static,
+    DstItem: 'static,
+    F: Fn(A, B) -> DstItem + Send + Sync + 'static,
+{
+    pub fn new(
+        a_port: OuterViewPort<dyn SingletonView<Item = A>>,
+        b_port: OuterViewPort<dyn SingletonView<Item = B>>,
+        f: F,
+        out_port: InnerViewPort<dyn SingletonView<Item = DstItem>>,
+    ) -> Arc<RwLock<Self>> {
+        let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
+
+        let map = Arc::new(RwLock::new(Map2Singleton {
+            a: proj_helper.new_singleton_arg(0, a_port, |s: &mut Self, msg| s.cast.notify(msg)),
+            b: proj_helper.new_singleton_arg(1, b_port, |s: &mut Self, msg| s.cast.notify(msg)),
+            f,
+            cast: out_port.get_broadcast(),
+            proj_helper,
+        }));
+
+        map.write().unwrap().proj_helper.set_proj(&map);
+        out_port.set_view(Some(map.clone()));
+        map
+    }
+}
+
+impl<A, B, DstItem, F> View for Map2Singleton<A, B, DstItem, F>
+where
+    A: 'static,
+    B: 'static,
+    DstItem: 'static,
+    F: Fn(A, B) -> DstItem + Send + Sync + 'static,
+{
+    type Msg = ();
+}
+
+impl<A, B, DstItem, F> SingletonView for Map2Singleton<A, B, DstItem, F>
+where
+    A: 'static,
+    B: 'static,
+    DstItem: 'static,
+    F: Fn(A, B) -> DstItem + Send + Sync + 'static,
+{
+    type Item = DstItem;
+
+    fn get(&self) -> DstItem {
+        (self.f)(get_arg(&self.a), get_arg(&self.b))
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct CombineSingleton<Item, DstItem, F>
+where
+    Item: 'static,
+    DstItem: 'static,
+    F: Fn(Vec<Item>) -> DstItem + Send + Sync + 'static,
+{
+    args: Vec<SingletonArg<Item>>,
+    f: F,
+    cast: Arc<RwLock<ObserverBroadcast<dyn SingletonView<Item = DstItem>>>>,
+    proj_helper: ProjectionHelper<usize, Self>,
+}
+
+impl<Item, DstItem, F> CombineSingleton<Item, DstItem, F>
+where
+    Item: 'static,
+    DstItem: 'static,
+    F: Fn(Vec<Item>) -> DstItem + Send + Sync + 'static,
+{
+    pub fn new(
+        ports: Vec<OuterViewPort<dyn SingletonView<Item = Item>>>,
+        f: F,
+        out_port: InnerViewPort<dyn SingletonView<Item = DstItem>>,
+    ) -> Arc<RwLock<Self>> {
+        let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
+
+        let combine = Arc::new(RwLock::new(CombineSingleton {
+            args: ports
+                .into_iter()
+                .enumerate()
+                .map(|(i, port)| proj_helper.new_singleton_arg(i, port, |s: &mut Self, msg| s.cast.notify(msg)))
+                .collect(),
+            f,
+            cast: out_port.get_broadcast(),
+            proj_helper,
+        }));
+
+        combine.write().unwrap().proj_helper.set_proj(&combine);
+        out_port.set_view(Some(combine.clone()));
+        combine
+    }
+}
+
+impl<Item, DstItem, F> View for CombineSingleton<Item, DstItem, F>
+where
+    Item: 'static,
+    DstItem: 'static,
+    F: Fn(Vec<Item>) -> DstItem + Send + Sync + 'static,
+{
+    type Msg = ();
+}
+
+impl<Item, DstItem, F> SingletonView for CombineSingleton<Item, DstItem, F>
+where
+    Item: 'static,
+    DstItem: 'static,
+    F: Fn(Vec<Item>) -> DstItem + Send + Sync + 'static,
+{
+    type Item = DstItem;
+
+    fn get(&self) -> DstItem {
+        (self.f)(self.args.iter().map(|arg| get_arg(arg)).collect())
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::singleton::*,
+        projection::combine_singleton::*,
+        view::port::UpdateTask,
+    };
+
+    #[test]
+    fn singleton_map2() {
+        let mut cursor_pos = SingletonBuffer::new(3);
+        let mut scroll_offset = SingletonBuffer::new(10);
+
+        let pos_port = cursor_pos.get_port().map2(&scroll_offset.get_port(), |c, s| c + s);
+        let pos_view = pos_port.get_view();
+        assert_eq!(pos_view.get(), 13);
+
+        let notified = Arc::new(RwLock::new(0));
+        let _obs = pos_port.add_notify_fn({
+            let notified = notified.clone();
+            move |_| *notified.write().unwrap() += 1
+        });
+        *notified.write().unwrap() = 0;
+
+        scroll_offset.set(20);
+        pos_port.0.update();
+        assert_eq!(*notified.read().unwrap(), 1);
+        assert_eq!(pos_view.get(), 23);
+
+        cursor_pos.set(4);
+        assert_eq!(pos_view.get(), 24);
+
+        let zipped = cursor_pos.get_port().zip(&scroll_offset.get_port());
+        assert_eq!(zipped.get_view().unwrap().get(), (4, 20));
+
+        let all = combine(
+            vec![cursor_pos.get_port(), scroll_offset.get_port(), SingletonBuffer::new(100).get_port()],
+            |xs| xs.into_iter().sum::<i32>(),
+        );
+        assert_eq!(all.get_view().get(), 124);
+
+        let triple = cursor_pos.get_port().map3(&scroll_offset.get_port(), &all, |a, b, c| a * b + c);
+        assert_eq!(triple.get_view().get(), 204);
+    }
+}
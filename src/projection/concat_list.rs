@@ -0,0 +1,175 @@
+use {
+    crate::{
+        view::{
+            InnerViewPort, Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
+            list::{ListView, ListDiff}
+        },
+        projection::projection_helper::ProjectionHelper,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> OuterViewPort<dyn ListView<Item>> {
+    /// all items of `self` followed by all items of `other`
+    pub fn concat(&self, other: &OuterViewPort<dyn ListView<Item>>) -> OuterViewPort<dyn ListView<Item>> {
+        let port = ViewPort::new();
+        ConcatList::new(self.clone(), other.clone(), port.inner());
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct ConcatList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    first: Arc<RwLock<Option<Arc<dyn ListView<Item>>>>>,
+    second: Arc<RwLock<Option<Arc<dyn ListView<Item>>>>>,
+
+    /// lengths as known from the received diffs
+    first_len: usize,
+    second_len: usize,
+
+    cast: Arc<RwLock<ObserverBroadcast<dyn ListView<Item>>>>,
+    proj_helper: ProjectionHelper<usize, Self>,
+}
+
+impl<Item> ConcatList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    pub fn new(
+        first_port: OuterViewPort<dyn ListView<Item>>,
+        second_port: OuterViewPort<dyn ListView<Item>>,
+        out_port: InnerViewPort<dyn ListView<Item>>,
+    ) -> Arc<RwLock<Self>> {
+        let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
+
+        let concat = Arc::new(RwLock::new(ConcatList {
+            first: proj_helper.new_list_arg(0, first_port, |s: &mut Self, diff| {
+                s.notify_first(diff);
+            }),
+            second: proj_helper.new_list_arg(1, second_port, |s: &mut Self, diff| {
+                s.notify_second(diff);
+            }),
+            first_len: 0,
+            second_len: 0,
+            cast: out_port.get_broadcast(),
+            proj_helper,
+        }));
+
+        concat.write().unwrap().proj_helper.set_proj(&concat);
+        out_port.set_view(Some(concat.clone()));
+        concat
+    }
+
+    fn notify_first(&mut self, diff: &ListDiff<Item>) {
+        match diff {
+            ListDiff::Clear => {
+                if self.second_len == 0 {
+                    self.cast.notify(&ListDiff::Clear);
+                } else {
+                    self.cast.notify_each((0..self.first_len).map(|_| ListDiff::Remove(0)));
+                }
+                self.first_len = 0;
+            }
+            ListDiff::Remove(idx) => {
+                self.first_len -= 1;
+                self.cast.notify(&ListDiff::Remove(*idx));
+            }
+            ListDiff::Insert{ .. } => {
+                self.first_len += 1;
+                self.cast.notify(diff);
+            }
+            ListDiff::Update{ .. } => {
+                self.cast.notify(diff);
+            }
+        }
+    }
+
+    fn notify_second(&mut self, diff: &ListDiff<Item>) {
+        let offset = self.first_len;
+        match diff {
+            ListDiff::Clear => {
+                if offset == 0 {
+                    self.cast.notify(&ListDiff::Clear);
+                } else {
+                    self.cast.notify_each((0..self.second_len).map(|_| ListDiff::Remove(offset)));
+                }
+                self.second_len = 0;
+            }
+            ListDiff::Remove(idx) => {
+                self.second_len -= 1;
+                self.cast.notify(&ListDiff::Remove(offset + idx));
+            }
+            ListDiff::Insert{ idx, val } => {
+                self.second_len += 1;
+                self.cast.notify(&ListDiff::Insert{ idx: offset + idx, val: val.clone() });
+            }
+            ListDiff::Update{ idx, val } => {
+                self.cast.notify(&ListDiff::Update{ idx: offset + idx, val: val.clone() });
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item> View for ConcatList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    type Msg = ListDiff<Item>;
+}
+
+impl<Item> ListView<Item> for ConcatList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn len(&self) -> Option<usize> {
+        Some(self.first_len + self.second_len)
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        if *idx < self.first_len {
+            self.first.get(idx)
+        } else {
+            self.second.get(&(*idx - self.first_len))
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::port::UpdateTask;
+
+    #[test]
+    fn concat_list1() {
+        let mut first = VecBuffer::with_data(vec![1, 2]);
+        let mut second = VecBuffer::with_data(vec![10]);
+        let target_port = first.get_port().to_list().concat(&second.get_port().to_list());
+
+        let mirror = VecBuffer::<i32>::new();
+        let _keepalive = mirror.attach_to(target_port.clone());
+        assert_eq!(mirror.len(), 3);
+
+        second.push(11);
+        first.insert(0, 0);
+        second.update(0, 20);
+        first.clear();
+        first.push(5);
+
+        target_port.0.update();
+        mirror.get_port().0.update();
+
+        assert_eq!(*mirror.get_port().get_view().unwrap().read().unwrap(), vec![5, 20, 11]);
+        assert_eq!(target_port.get_view().unwrap().get(&1), Some(20));
+    }
+}
@@ -28,7 +28,7 @@ where T: Send + Sync + 'static
 impl<T> View for Wrapped<T>
 where T: Clone + Send + Sync + 'static
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<T> SequenceView for Wrapped<T>
@@ -72,9 +72,11 @@ where T: Clone + Send + Sync + 'static
         let w = Arc::new(RwLock::new(Wrapped {
             opening,
             closing,
-            items: proj_helper.new_sequence_arg((), self.clone(), |s: &mut Wrapped<T>, item_idx| {
-                s.cast.notify(&(*item_idx + 1));
-                s.cast.notify(&(*item_idx + 2));
+            items: proj_helper.new_sequence_arg((), self.clone(), |s: &mut Wrapped<T>, diff| {
+                s.cast.notify(&match diff {
+                    SeqDiff::Range(r) => SeqDiff::Range(r.start + 1..r.end + 1),
+                    SeqDiff::Shift { at, by } => SeqDiff::Shift { at: at + 1, by: *by },
+                });
             }),
             cast: port.get_cast(),
             proj_helper,
@@ -95,6 +97,7 @@ where T: Send + Sync + 'static
 {
     pub(super) delimiter: T,
     pub(super) items: Arc<dyn SequenceView<Item = T>>,
+    pub(super) items_len: usize,
 
     pub(super) cast: Arc<RwLock<ObserverBroadcast<dyn SequenceView<Item = T>>>>,
     pub(super) proj_helper: ProjectionHelper<(), Self>,
@@ -103,7 +106,7 @@ where T: Send + Sync + 'static
 impl<T> View for Separated<T>
 where T: Clone + Send + Sync + 'static    
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<T> SequenceView for Separated<T>
@@ -152,13 +155,28 @@ where T: Clone + Send + Sync + 'static
             items: proj_helper.new_sequence_arg(
                 (),
                 self.clone(),
-                |s: &mut Separated<T>, item_idx| {
-                    s.cast.notify(&(*item_idx * 2));
-                    if *item_idx > 0 {
-                        s.cast.notify(&(*item_idx * 2 - 1));
+                |s: &mut Separated<T>, diff| {
+                    let old_len = s.items_len;
+                    s.items_len = diff.apply_len(old_len);
+
+                    match diff {
+                        SeqDiff::Range(r) => if !r.is_empty() {
+                            // delimiters stay the same
+                            s.cast.notify(&SeqDiff::Range(r.start * 2..r.end * 2 - 1));
+                        }
+                        SeqDiff::Shift { at, by } => {
+                            // every item comes with one delimiter,
+                            // except if the sequence is or was empty
+                            let n = by.abs() * 2;
+                            let n = if old_len == 0 || s.items_len == 0 { n - 1 } else { n };
+                            s.cast.notify(&SeqDiff::Shift {
+                                at: if *at == 0 { 0 } else { at * 2 - 1 },
+                                by: n * by.signum(),
+                            });
+                        }
                     }
                 }),
-                    
+            items_len: 0,
             cast: port.get_cast(),
             proj_helper,
         }));
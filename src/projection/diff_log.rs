@@ -0,0 +1,428 @@
+use {
+    crate::{
+        buffer::{index_hashmap::IndexBuffer, singleton::SingletonBuffer, vec::{VecBuffer, VecDiff}},
+        projection::{
+            serialize_diff::*,
+            view2bin::{apply_bin_sync, write_frames},
+        },
+        view::{index::IndexView, singleton::SingletonView, Observer, OuterViewPort, View},
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    std::sync::RwLock,
+    std::{
+        fs::{self, File},
+        hash::Hash,
+        io::{self, BufReader},
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Persistence Observer which appends every diff of a view to a file
+/// and replaces the log by a snapshot of the view after `compact_after` diffs.
+/// The snapshot is only taken at the end of an update, when the view
+/// matches the diffs written so far.
+///
+/// Generation `n` consists of the files `snapshot.n`, whose diffs rebuild the view
+/// from scratch, and `log.n` with all diffs since. A snapshot is written to a
+/// temporary file and renamed into place, and the directory is synced before the
+/// previous generation is removed, so it always contains one complete generation.
+pub struct DiffLog<E>
+where
+    E: DiffEncoder + Default,
+{
+    dir: PathBuf,
+    generation: u64,
+    compact_after: usize,
+
+    encoder: E,
+    view: Option<Arc<E::View>>,
+
+    log: Option<File>,
+    /// number of diffs in the current log file
+    len: usize,
+
+    error: WriteError,
+}
+
+impl<E> DiffLog<E>
+where
+    E: DiffEncoder + Default,
+    E::Diff: Serialize,
+{
+    /// the error which stopped this log
+    pub fn error(&self) -> Option<&SerializeError> {
+        self.error.get()
+    }
+
+    /// generation of the files currently written
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// start a new generation with a snapshot of the current view
+    pub fn compact(&mut self) {
+        let result = self.try_compact();
+        self.error.handle(result);
+    }
+
+    fn file(&self, name: &str, generation: u64) -> PathBuf {
+        self.dir.join(format!("{}.{}", name, generation))
+    }
+
+    fn try_compact(&mut self) -> Result<(), SerializeError> {
+        fs::create_dir_all(&self.dir)?;
+        if self.log.is_none() {
+            self.generation = latest_generation(&self.dir)?.unwrap_or(0);
+        }
+        let generation = self.generation + 1;
+
+        let mut encoder = E::default();
+        let tmp = self.dir.join(format!("snapshot.{}.tmp", generation));
+        let mut snapshot = File::create(&tmp)?;
+        write_frames(&mut snapshot, encoder.reset(self.view.clone())?)?;
+        snapshot.sync_all()?;
+        fs::rename(&tmp, self.file("snapshot", generation))?;
+        self.log = Some(File::create(self.file("log", generation))?);
+
+        // the new generation has to be durable before the previous one is removed
+        File::open(&self.dir)?.sync_all()?;
+        for name in ["snapshot", "log"] {
+            match fs::remove_file(self.file(name, self.generation)) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+
+        self.generation = generation;
+        self.encoder = encoder;
+        self.len = 0;
+        Ok(())
+    }
+
+    fn try_append(&mut self, diffs: Vec<E::Diff>) -> Result<(), SerializeError> {
+        self.len += diffs.len();
+        if let Some(log) = self.log.as_mut() {
+            write_frames(log, diffs)?;
+            log.sync_data()?;
+        }
+        Ok(())
+    }
+}
+
+impl<E> Observer<E::View> for DiffLog<E>
+where
+    E: DiffEncoder + Default,
+    E::Diff: Serialize,
+{
+    fn reset(&mut self, view: Option<Arc<E::View>>) {
+        self.view = view;
+        if !self.error.is_set() {
+            self.compact();
+        }
+    }
+
+    fn notify(&mut self, msg: &<E::View as View>::Msg) {
+        if !self.error.is_set() {
+            let result = match self.encoder.encode(msg) {
+                Ok(diffs) => self.try_append(diffs),
+                Err(err) => Err(err),
+            };
+            self.error.handle(result);
+        }
+    }
+
+    fn end_update(&mut self) {
+        if !self.error.is_set() && self.log.is_some() && self.len >= self.compact_after {
+            self.compact();
+        }
+    }
+}
+
+impl<V: View + ?Sized + 'static> OuterViewPort<V>
+where
+    V::Msg: Clone,
+{
+    /// Attaching the log starts a new generation,
+    /// so a buffer should be restored from `dir` before.
+    /// `on_error` is invoked on the first error, including one in the initial snapshot
+    pub fn log_diffs_with<E>(
+        &self,
+        dir: impl AsRef<Path>,
+        compact_after: usize,
+        on_error: Option<ErrorCallback>,
+    ) -> Arc<RwLock<DiffLog<E>>>
+    where
+        E: DiffEncoder<View = V> + Default + 'static,
+        E::Diff: Serialize,
+    {
+        let log = Arc::new(RwLock::new(DiffLog {
+            dir: dir.as_ref().to_path_buf(),
+            generation: 0,
+            compact_after,
+            encoder: E::default(),
+            view: None,
+            log: None,
+            len: 0,
+            error: WriteError::new(on_error),
+        }));
+        self.add_observer(log.clone());
+        log
+    }
+}
+
+/// highest generation of a complete snapshot in `dir`
+fn latest_generation(dir: &Path) -> io::Result<Option<u64>> {
+    let mut latest = None;
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let generation = name.to_str()
+            .and_then(|name| name.strip_prefix("snapshot."))
+            .and_then(|n| n.parse::<u64>().ok());
+        latest = latest.max(generation);
+    }
+    Ok(latest)
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// apply the latest snapshot in `dir` and the log following it to `target`.
+/// A truncated diff at the end of the log, as left by an interrupted write, is ignored.
+pub fn restore_log<D, B>(target: &mut B, dir: &Path) -> Result<(), DiffDecodeError>
+where
+    D: DeserializeOwned,
+    B: ApplyDiff<D>,
+{
+    let open = |path: PathBuf| match File::open(path) {
+        Ok(file) => Ok(Some(BufReader::new(file))),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(DiffDecodeError::Io { frame: 1, error }),
+    };
+
+    let generation = match latest_generation(dir) {
+        Ok(Some(generation)) => generation,
+        Ok(None) => return Ok(()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(DiffDecodeError::Io { frame: 1, error }),
+    };
+
+    if let Some(snapshot) = open(dir.join(format!("snapshot.{}", generation)))? {
+        apply_bin_sync::<D, _, _>(target, snapshot)?;
+    }
+    if let Some(log) = open(dir.join(format!("log.{}", generation)))? {
+        match apply_bin_sync::<D, _, _>(target, log) {
+            Ok(()) | Err(DiffDecodeError::Truncated { .. }) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<T> OuterViewPort<RwLock<Vec<T>>>
+where
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn log_diffs(&self, dir: impl AsRef<Path>, compact_after: usize) -> Arc<RwLock<DiffLog<VecEncoder<T>>>> {
+        self.log_diffs_with(dir, compact_after, None)
+    }
+}
+
+impl<T> OuterViewPort<dyn SingletonView<Item = T>>
+where
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn log_diffs(&self, dir: impl AsRef<Path>, compact_after: usize) -> Arc<RwLock<DiffLog<SingletonEncoder<T>>>> {
+        self.log_diffs_with(dir, compact_after, None)
+    }
+}
+
+impl<Key, T> OuterViewPort<dyn IndexView<Key, Item = T>>
+where
+    Key: Clone + Hash + Eq + Serialize + Send + Sync + 'static,
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn log_diffs(&self, dir: impl AsRef<Path>, compact_after: usize) -> Arc<RwLock<DiffLog<IndexEncoder<Key, T>>>> {
+        self.log_diffs_with(dir, compact_after, None)
+    }
+}
+
+impl<T> VecBuffer<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// restore the contents written by `log_diffs()`
+    pub fn restore_log(&mut self, dir: impl AsRef<Path>) -> Result<(), DiffDecodeError> {
+        restore_log::<VecDiff<T>, _>(self, dir.as_ref())
+    }
+}
+
+impl<T> SingletonBuffer<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// restore the value written by `log_diffs()`
+    pub fn restore_log(&mut self, dir: impl AsRef<Path>) -> Result<(), DiffDecodeError> {
+        restore_log::<T, _>(self, dir.as_ref())
+    }
+}
+
+impl<Key, T> IndexBuffer<Key, T>
+where
+    Key: DeserializeOwned + Clone + Hash + Eq + Send + Sync + 'static,
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// restore the contents written by `log_diffs()`
+    pub fn restore_log(&mut self, dir: impl AsRef<Path>) -> Result<(), DiffDecodeError> {
+        restore_log::<IndexUpdate<Key, T>, _>(self, dir.as_ref())
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            buffer::{index_hashmap::*, singleton::*, vec::*},
+            projection::serialize_diff::{SerializeError, VecEncoder},
+            view::port::UpdateTask,
+        },
+        std::{io::Write, path::PathBuf},
+    };
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("r3vi-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn restored(dir: &PathBuf) -> Vec<u32> {
+        let mut dst = VecBuffer::new();
+        dst.restore_log(dir).unwrap();
+        let data = dst.get_port().get_view().unwrap().read().unwrap().clone();
+        data
+    }
+
+    #[test]
+    fn diff_log_vec() {
+        let dir = test_dir("diff-log-vec");
+        let mut src = VecBuffer::<u32>::with_data(vec![1, 2]);
+        let log = src.get_port().log_diffs(&dir, 4);
+        assert_eq!(log.read().unwrap().generation(), 1);
+        assert_eq!(restored(&dir), vec![1, 2]);
+
+        src.push(3);
+        src.remove(0);
+        src.get_port().0.update();
+        assert_eq!(restored(&dir), vec![2, 3]);
+
+        // the fourth diff starts a new generation
+        src.push(4);
+        src.push(5);
+        src.get_port().0.update();
+        assert_eq!(log.read().unwrap().generation(), 2);
+        assert!(!dir.join("snapshot.1").exists());
+        assert!(!dir.join("log.1").exists());
+        assert_eq!(restored(&dir), vec![2, 3, 4, 5]);
+
+        // a write interrupted by a crash
+        src.update(0, 20);
+        src.get_port().0.update();
+        let mut file = std::fs::OpenOptions::new().append(true).open(dir.join("log.2")).unwrap();
+        file.write_all(&[9, 0, 0, 0, 0]).unwrap();
+        assert_eq!(restored(&dir), vec![20, 3, 4, 5]);
+
+        // attaching a restored buffer continues with the next generation
+        let dst = {
+            let mut dst = VecBuffer::<u32>::new();
+            dst.restore_log(&dir).unwrap();
+            dst
+        };
+        drop(log);
+        let log = dst.get_port().log_diffs(&dir, 4);
+        assert_eq!(log.read().unwrap().generation(), 3);
+        assert!(log.read().unwrap().error().is_none());
+        assert_eq!(restored(&dir), vec![20, 3, 4, 5]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn diff_log_compact_within_update() {
+        // the snapshot must not contain diffs which are still to be logged
+        let dir = test_dir("diff-log-compact");
+        let mut src = VecBuffer::<u32>::with_data(vec![1]);
+        let log = src.get_port().log_diffs(&dir, 2);
+
+        src.push(2);
+        src.push(3);
+        src.push(4);
+        src.get_port().0.update();
+        assert_eq!(log.read().unwrap().generation(), 2);
+        assert_eq!(restored(&dir), vec![1, 2, 3, 4]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn diff_log_index_singleton() {
+        let dir = test_dir("diff-log-index");
+        let mut src = IndexBuffer::<u32, char>::new();
+        src.insert(1, 'a');
+        let _log = src.get_port().log_diffs(&dir, 100);
+
+        src.insert(2, 'b');
+        src.remove(1);
+        src.get_port().0.update();
+
+        let mut dst = IndexBuffer::<u32, char>::new();
+        dst.restore_log(&dir).unwrap();
+        assert_eq!(dst.get(&1), None);
+        assert_eq!(dst.get(&2), Some('b'));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let dir = test_dir("diff-log-singleton");
+        let mut src = SingletonBuffer::new(1);
+        let _log = src.get_port().log_diffs(&dir, 1);
+        src.set(2);
+        src.get_port().0.update();
+
+        let mut dst = SingletonBuffer::new(0);
+        dst.restore_log(&dir).unwrap();
+        assert_eq!(dst.get(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn diff_log_error() {
+        // the directory can not be created below a file
+        let file = test_dir("diff-log-error");
+        std::fs::write(&file, b"").unwrap();
+
+        let errors = std::sync::Arc::new(std::sync::Mutex::new(0));
+        let mut src = VecBuffer::<u32>::with_data(vec![1]);
+        let log = src.get_port().log_diffs_with::<VecEncoder<u32>>(
+            file.join("log"),
+            4,
+            Some(Box::new({
+                let errors = errors.clone();
+                move |err| {
+                    assert!(matches!(err, SerializeError::Io(_)));
+                    *errors.lock().unwrap() += 1;
+                }
+            })),
+        );
+        assert_eq!(*errors.lock().unwrap(), 1);
+        assert!(log.read().unwrap().error().is_some());
+
+        src.push(2);
+        src.get_port().0.update();
+        assert_eq!(*errors.lock().unwrap(), 1);
+
+        std::fs::remove_file(&file).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+use {
+    crate::{
+        view::{
+            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            singleton::SingletonView,
+        }
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item> OuterViewPort<dyn SingletonView<Item = Item>>
+where
+    Item: Clone + PartialEq + Send + Sync + 'static,
+{
+    /// only notifies when the value is not equal to the last notified one
+    pub fn distinct(&self) -> OuterViewPort<dyn SingletonView<Item = Item>> {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let distinct = Arc::new(RwLock::new(DistinctSingleton {
+            src_view: None,
+            last: None,
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(distinct.clone());
+        port.inner().set_view(Some(distinct));
+        port.into_outer()
+    }
+
+    /// the source value if it satisfies `pred`, `fallback` otherwise.
+    /// rejected values all map to `fallback`, so no-op notifications are suppressed
+    pub fn filter<P: Fn(&Item) -> bool + Send + Sync + 'static>(
+        &self,
+        pred: P,
+        fallback: Item,
+    ) -> OuterViewPort<dyn SingletonView<Item = Item>> {
+        self.map(move |x| if pred(&x) { x } else { fallback.clone() })
+            .distinct()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct DistinctSingleton<SrcView>
+where
+    SrcView: SingletonView + ?Sized,
+    SrcView::Item: Clone + PartialEq + Send + Sync,
+{
+    src_view: Option<Arc<SrcView>>,
+    last: Option<SrcView::Item>,
+    cast: Arc<RwLock<ObserverBroadcast<dyn SingletonView<Item = SrcView::Item>>>>,
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<SrcView> View for DistinctSingleton<SrcView>
+where
+    SrcView: SingletonView + ?Sized,
+    SrcView::Item: Clone + PartialEq + Send + Sync,
+{
+    type Msg = ();
+}
+
+impl<SrcView> SingletonView for DistinctSingleton<SrcView>
+where
+    SrcView: SingletonView + ?Sized,
+    SrcView::Item: Clone + PartialEq + Send + Sync,
+{
+    type Item = SrcView::Item;
+
+    fn get(&self) -> Self::Item {
+        self.src_view.as_ref().unwrap().get()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<SrcView> Observer<SrcView> for DistinctSingleton<SrcView>
+where
+    SrcView: SingletonView + ?Sized,
+    SrcView::Item: Clone + PartialEq + Send + Sync,
+{
+    fn reset(&mut self, view: Option<Arc<SrcView>>) {
+        self.src_view = view;
+        self.last = self.src_view.as_ref().map(|v| v.get());
+        self.cast.notify(&());
+    }
+
+    fn notify(&mut self, msg: &()) {
+        if let Some(v) = self.src_view.as_ref() {
+            let val = v.get();
+            if self.last.as_ref() != Some(&val) {
+                self.last = Some(val);
+                self.cast.notify(msg);
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::singleton::*,
+        view::{port::UpdateTask, singleton::SingletonView},
+    };
+
+    #[test]
+    fn singleton_distinct1() {
+        let mut buffer = SingletonBuffer::new(3);
+
+        let parity = buffer.get_port().map(|x| x % 2).distinct();
+        let clamped = buffer.get_port().filter(|x| *x < 10, 10);
+
+        let notified = std::sync::Arc::new(std::sync::RwLock::new(0));
+        let _obs = parity.add_notify_fn({
+            let notified = notified.clone();
+            move |_| *notified.write().unwrap() += 1
+        });
+        *notified.write().unwrap() = 0;
+
+        buffer.set(5);
+        parity.0.update();
+        assert_eq!(*notified.read().unwrap(), 0);
+
+        buffer.set(6);
+        parity.0.update();
+        assert_eq!(*notified.read().unwrap(), 1);
+        assert_eq!(parity.get_view().get(), 0);
+
+        let raw_notified = std::sync::Arc::new(std::sync::RwLock::new(0));
+        let _raw_obs = buffer.get_port().add_notify_fn({
+            let raw_notified = raw_notified.clone();
+            move |_| *raw_notified.write().unwrap() += 1
+        });
+        *raw_notified.write().unwrap() = 0;
+
+        assert!(!buffer.set_if_changed(6));
+        assert!(buffer.set_if_changed(8));
+        buffer.get_port().0.update();
+        assert_eq!(*raw_notified.read().unwrap(), 1);
+
+        assert_eq!(clamped.get_view().get(), 8);
+        buffer.set(12);
+        assert_eq!(clamped.get_view().get(), 10);
+    }
+}
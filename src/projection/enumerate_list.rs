@@ -0,0 +1,149 @@
+use {
+    crate::{
+        view::{
+            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            list::{ListView, ListViewExt, ListDiff}
+        },
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> OuterViewPort<dyn ListView<Item>> {
+    pub fn enumerate(&self) -> OuterViewPort<dyn ListView<(usize, Item)>> {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let enumerate = Arc::new(RwLock::new(EnumerateList {
+            items: Vec::new(),
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(enumerate.clone());
+        port.inner().set_view(Some(enumerate));
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+type EnumeratedCast<Item> = Arc<RwLock<ObserverBroadcast<dyn ListView<(usize, Item)>>>>;
+
+/// Pairs every item with its position.
+/// Inserting or removing an item shifts the positions of all following items,
+/// which are then sent as updates.
+pub struct EnumerateList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    /// items as known from the received diffs
+    items: Vec<Item>,
+    cast: EnumeratedCast<Item>,
+}
+
+impl<Item> EnumerateList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn update_positions(&mut self, start: usize) {
+        for idx in start..self.items.len() {
+            let val = (idx, self.items[idx].clone());
+            self.cast.notify(&ListDiff::Update{ idx, val });
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item> View for EnumerateList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    type Msg = ListDiff<(usize, Item)>;
+}
+
+impl<Item> ListView<(usize, Item)> for EnumerateList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn len(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+
+    fn get(&self, idx: &usize) -> Option<(usize, Item)> {
+        Some((*idx, self.items.get(*idx)?.clone()))
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item> Observer<dyn ListView<Item>> for EnumerateList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn reset(&mut self, view: Option<Arc<dyn ListView<Item>>>) {
+        self.items = view.map(|v| v.iter().collect()).unwrap_or_default();
+
+        self.cast.notify(&ListDiff::Clear);
+        for (idx, val) in self.items.iter().cloned().enumerate() {
+            self.cast.notify(&ListDiff::Insert{ idx, val: (idx, val) });
+        }
+    }
+
+    fn notify(&mut self, msg: &ListDiff<Item>) {
+        match msg {
+            ListDiff::Clear => {
+                self.items.clear();
+                self.cast.notify(&ListDiff::Clear);
+            }
+            ListDiff::Remove(idx) => {
+                self.items.remove(*idx);
+                self.cast.notify(&ListDiff::Remove(*idx));
+                self.update_positions(*idx);
+            }
+            ListDiff::Insert{ idx, val } => {
+                self.items.insert(*idx, val.clone());
+                self.cast.notify(&ListDiff::Insert{ idx: *idx, val: (*idx, val.clone()) });
+                self.update_positions(*idx + 1);
+            }
+            ListDiff::Update{ idx, val } => {
+                self.items[*idx] = val.clone();
+                self.cast.notify(&ListDiff::Update{ idx: *idx, val: (*idx, val.clone()) });
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::port::UpdateTask;
+
+    #[test]
+    fn enumerate_list1() {
+        let mut buffer = VecBuffer::with_data(vec!['b', 'c']);
+        let target_port = buffer.get_port().to_list().enumerate();
+
+        let mirror = VecBuffer::<(usize, char)>::new();
+        let _keepalive = mirror.attach_to(target_port.clone());
+        assert_eq!(mirror.get(1), (1, 'c'));
+
+        buffer.insert(0, 'a');
+        buffer.push('d');
+        buffer.remove(1);
+        buffer.update(0, 'x');
+
+        target_port.0.update();
+        mirror.get_port().0.update();
+
+        assert_eq!(
+            *mirror.get_port().get_view().unwrap().read().unwrap(),
+            vec![(0, 'x'), (1, 'c'), (2, 'd')]
+        );
+        assert_eq!(target_port.get_view().unwrap().get(&2), Some((2, 'd')));
+    }
+}
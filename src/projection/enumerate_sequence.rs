@@ -2,11 +2,12 @@ use {
     crate::{
         view::{
             Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
-            sequence::SequenceView,
+            sequence::{SeqDiff, SequenceView},
         }
     },
     std::sync::Arc,
     std::sync::RwLock,
+    std::cmp::max,
 };
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
@@ -18,6 +19,7 @@ impl<Item: 'static> OuterViewPort<dyn SequenceView<Item = Item>> {
 
         let view = Arc::new(RwLock::new(EnumerateSequence {
             src_view: None,
+            cur_len: 0,
             cast: port.inner().get_broadcast(),
         }));
 
@@ -34,6 +36,7 @@ where
     SrcView: SequenceView + ?Sized,
 {
     src_view: Option<Arc<SrcView>>,
+    cur_len: usize,
     cast: Arc<RwLock<ObserverBroadcast<dyn SequenceView<Item = (usize, SrcView::Item)>>>>,
 }
 
@@ -43,7 +46,7 @@ impl<SrcView> View for EnumerateSequence<SrcView>
 where
     SrcView: SequenceView + ?Sized,
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<SrcView> SequenceView for EnumerateSequence<SrcView>
@@ -68,20 +71,24 @@ where
     SrcView: SequenceView + ?Sized
 {
     fn reset(&mut self, view: Option<Arc<SrcView>>) {
-        let old_len = self.len();
+        let old_len = self.cur_len;
         self.src_view = view;
-        let new_len = self.len();
+        self.cur_len = self.src_view.len().unwrap_or(0);
 
-        if let Some(len) = old_len {
-            self.cast.notify_each(0..len);
-        }
-        if let Some(len) = new_len {
-            self.cast.notify_each(0..len);
-        }
+        self.cast.notify_each(SeqDiff::replace(old_len, self.cur_len));
     }
 
-    fn notify(&mut self, msg: &usize) {
+    fn notify(&mut self, msg: &SeqDiff) {
+        self.cur_len = msg.apply_len(self.cur_len);
         self.cast.notify(msg);
+
+        if let SeqDiff::Shift { at, by } = msg {
+            // the moved items carry their new index
+            let moved = at + max(*by, 0) as usize..self.cur_len;
+            if !moved.is_empty() {
+                self.cast.notify(&SeqDiff::Range(moved));
+            }
+        }
     }
 }
 
@@ -0,0 +1,209 @@
+use {
+    crate::{
+        view::{
+            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            list::{ListView, ListViewExt, ListDiff}
+        },
+        projection::flag_tree::FlagTree,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub(crate) type FilterListView<Item, P> = Arc<RwLock<FilterList<Item, dyn ListView<Item>, P>>>;
+
+impl<Item: Clone + Send + Sync + 'static> OuterViewPort<dyn ListView<Item>> {
+    pub fn filter<P: Fn(&Item) -> bool + Send + Sync + 'static>(
+        &self,
+        pred: P,
+    ) -> OuterViewPort<dyn ListView<Item>> {
+        self.filter_list(pred).0
+    }
+
+    /// filtered port along with its view, which translates positions to the source
+    pub(crate) fn filter_list<P: Fn(&Item) -> bool + Send + Sync + 'static>(
+        &self,
+        pred: P,
+    ) -> (OuterViewPort<dyn ListView<Item>>, FilterListView<Item, P>) {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let filter = Arc::new(RwLock::new(FilterList {
+            src_view: None,
+            pred,
+            preds: FlagTree::new(),
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(filter.clone());
+        port.inner().set_view(Some(filter.clone()));
+        (port.into_outer(), filter)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Keeps the predicate of every source item,
+/// so each source diff is translated into at most one filtered diff
+/// and indices are translated by rank/select in logarithmic time.
+pub struct FilterList<Item, SrcView, P>
+where
+    Item: Clone + Send + Sync + 'static,
+    SrcView: ListView<Item> + ?Sized,
+    P: Fn(&Item) -> bool + Send + Sync,
+{
+    src_view: Option<Arc<SrcView>>,
+    pred: P,
+    preds: FlagTree,
+    cast: Arc<RwLock<ObserverBroadcast<dyn ListView<Item>>>>,
+}
+
+impl<Item, SrcView, P> FilterList<Item, SrcView, P>
+where
+    Item: Clone + Send + Sync + 'static,
+    SrcView: ListView<Item> + ?Sized,
+    P: Fn(&Item) -> bool + Send + Sync,
+{
+    /// source index of the filtered position `idx`
+    pub fn select(&self, idx: usize) -> Option<usize> {
+        self.preds.select(idx)
+    }
+}
+
+impl<Item, SrcView, P> View for FilterList<Item, SrcView, P>
+where
+    Item: Clone + Send + Sync + 'static,
+    SrcView: ListView<Item> + ?Sized,
+    P: Fn(&Item) -> bool + Send + Sync,
+{
+    type Msg = ListDiff<Item>;
+}
+
+impl<Item, SrcView, P> ListView<Item> for FilterList<Item, SrcView, P>
+where
+    Item: Clone + Send + Sync + 'static,
+    SrcView: ListView<Item> + ?Sized,
+    P: Fn(&Item) -> bool + Send + Sync,
+{
+    fn len(&self) -> Option<usize> {
+        Some(self.preds.count())
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        self.src_view.get(&self.preds.select(*idx)?)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item, SrcView, P> Observer<SrcView> for FilterList<Item, SrcView, P>
+where
+    Item: Clone + Send + Sync + 'static,
+    SrcView: ListView<Item> + ?Sized,
+    P: Fn(&Item) -> bool + Send + Sync,
+{
+    fn reset(&mut self, view: Option<Arc<SrcView>>) {
+        self.src_view = view;
+        self.cast.notify(&ListDiff::Clear);
+
+        let vals: Vec<Item> = self.src_view.iter().flat_map(|v| v.iter()).collect();
+        let flags: Vec<bool> = vals.iter().map(|val| (self.pred)(val)).collect();
+        self.preds = FlagTree::from_flags(flags.iter().cloned());
+
+        let inserts = vals.into_iter().zip(flags).filter(|(_, p)| *p).map(|(val, _)| val);
+        for (idx, val) in inserts.enumerate() {
+            self.cast.notify(&ListDiff::Insert { idx, val });
+        }
+    }
+
+    fn notify(&mut self, msg: &ListDiff<Item>) {
+        let forwarded_msg =
+            match msg {
+                ListDiff::Clear => {
+                    self.preds = FlagTree::new();
+                    Some(ListDiff::Clear)
+                }
+                ListDiff::Remove(idx) => {
+                    if self.preds.remove(*idx..*idx + 1) > 0 {
+                        Some(ListDiff::Remove(self.preds.rank(*idx)))
+                    } else {
+                        None
+                    }
+                }
+                ListDiff::Insert{ idx, val } => {
+                    let p = (self.pred)(val);
+                    self.preds.insert(*idx, [p]);
+                    if p {
+                        Some(ListDiff::Insert{ idx: self.preds.rank(*idx), val: val.clone() })
+                    } else {
+                        None
+                    }
+                }
+                ListDiff::Update{ idx, val } => {
+                    let new_pred = (self.pred)(val);
+                    let old_pred = self.preds.set(*idx, new_pred);
+                    let i = self.preds.rank(*idx);
+
+                    match (old_pred, new_pred) {
+                        (true, true) => Some(ListDiff::Update{ idx: i, val: val.clone() }),
+                        (false, true) => Some(ListDiff::Insert{ idx: i, val: val.clone() }),
+                        (true, false) => Some(ListDiff::Remove(i)),
+                        (false, false) => None
+                    }
+                }
+            };
+
+        if let Some(msg) = forwarded_msg {
+            self.cast.notify(&msg);
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::{port::UpdateTask, list::ListDiff};
+
+    #[test]
+    fn filter_list1() {
+        let mut buffer = VecBuffer::with_data(vec![1, 12, 3]);
+        let target_port = buffer.get_port().to_list().filter(|x| *x > 10);
+
+        let mirror = VecBuffer::<i32>::new();
+        let _keepalive = mirror.attach_to(target_port.clone());
+
+        let diffs = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _obs = target_port.add_notify_fn({
+            let diffs = diffs.clone();
+            move |msg| diffs.lock().unwrap().push(match msg {
+                ListDiff::Clear => "clear".to_string(),
+                ListDiff::Remove(idx) => format!("remove {}", idx),
+                ListDiff::Insert{ idx, val } => format!("insert {} {}", idx, val),
+                ListDiff::Update{ idx, val } => format!("update {} {}", idx, val),
+            })
+        });
+
+        buffer.push(20);     // insert
+        buffer.insert(0, 5); // filtered out
+        buffer.update(1, 2); // 1 -> 2, still filtered out
+        buffer.update(3, 4); // 3 -> 4, still filtered out
+        buffer.update(2, 15); // 12 -> 15
+        buffer.update(3, 30); // 4 -> 30, becomes visible
+        buffer.update(4, 0); // 20 -> 0, disappears
+        buffer.remove(2);    // 15
+
+        target_port.0.update();
+        mirror.get_port().0.update();
+
+        assert_eq!(
+            *diffs.lock().unwrap(),
+            vec!["insert 1 20", "update 0 15", "insert 1 30", "remove 2", "remove 0"]
+        );
+        assert_eq!(*mirror.get_port().get_view().unwrap().read().unwrap(), vec![30]);
+        assert_eq!(target_port.get_view().unwrap().get(&0), Some(30));
+    }
+}
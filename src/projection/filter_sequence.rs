@@ -2,8 +2,10 @@ use {
     crate::{
         view::{
             Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
-            sequence::SequenceView,
+            port::UpdateTask,
+            sequence::{SeqDiff, SeqDiffBatch, SequenceView},
         },
+        projection::flag_tree::FlagTree,
     },
     std::sync::Arc,
     std::sync::RwLock,
@@ -17,57 +19,105 @@ impl<V: SequenceView + ?Sized + 'static> OuterViewPort<V> {
         pred: P,
     ) -> OuterViewPort<dyn SequenceView<Item = V::Item>> {
         let port = ViewPort::new();
-        port.add_update_hook(Arc::new(self.0.clone()));
+        let src: Arc<dyn UpdateTask> = Arc::new(self.0.clone());
+        port.add_update_hook(src.clone());
 
-        let filter = Arc::new(RwLock::new(Filter {
+        let state = Arc::new(RwLock::new(FilterState {
             src_view: None,
             pred,
-            old_preds: RwLock::new(Vec::new()),
+            old_preds: FlagTree::new(),
+            pending: SeqDiffBatch::default(),
             cast: port.inner().get_broadcast(),
         }));
 
-        self.add_observer(filter.clone());
-        port.inner().set_view(Some(filter));
+        self.add_observer(state.clone());
+        port.inner().set_view(Some(Arc::new(Filter { src, state })));
         port.into_outer()
     }
 }
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
+/// The predicate of every source item is cached,
+/// so translating between filtered and source indices
+/// is a rank/select query in logarithmic time.
+///
+/// Reading the view first delivers all pending messages of the source,
+/// so it is up to date even if no one updates the filtered port.
 struct Filter<SrcView, P>
+where
+    SrcView: SequenceView + ?Sized + 'static,
+    P: Fn(&SrcView::Item) -> bool + Send + Sync + 'static,
+{
+    src: Arc<dyn UpdateTask>,
+    state: Arc<RwLock<FilterState<SrcView, P>>>,
+}
+
+struct FilterState<SrcView, P>
 where
     SrcView: SequenceView + ?Sized + 'static,
     P: Fn(&SrcView::Item) -> bool + Send + Sync + 'static,
 {
     src_view: Option<Arc<SrcView>>,
     pred: P,
-    old_preds: RwLock<Vec<bool>>,
+    old_preds: FlagTree,
+
+    /// messages of the running update, the predicates
+    /// are only evaluated once the update is complete
+    pending: SeqDiffBatch,
     cast: Arc<RwLock<ObserverBroadcast<dyn SequenceView<Item = SrcView::Item>>>>,
 }
 
-impl<SrcView, P> Filter<SrcView, P>
+impl<SrcView, P> FilterState<SrcView, P>
 where
     SrcView: SequenceView + ?Sized + 'static,
     P: Fn(&SrcView::Item) -> bool + Send + Sync + 'static,
 {
-    fn get_offset(&self, idx: usize) -> usize {
-        if let Some(v) = self.src_view.clone() {
-            let mut i = 0;
-            let mut j = 0;
-            let mut offset = 0;
-
-            while let (Some(x), true) = (v.get(&i), j <= idx) {
-                if (self.pred)(&x) {
-                    j += 1;
-                } else {
-                    offset += 1;
+    fn eval_pred(&self, idx: usize) -> bool {
+        if let Some(x) = self.src_view.get(&idx) {
+            (self.pred)(&x)
+        } else {
+            false
+        }
+    }
+
+    fn apply(&mut self, diff: &SeqDiff) {
+        match diff {
+            SeqDiff::Range(r) => {
+                let mut diffs = Vec::new();
+                for idx in r.clone() {
+                    let np = self.eval_pred(idx);
+                    let op = self.old_preds.set(idx, np);
+                    let i = self.old_preds.rank(idx);
+
+                    if np != op {
+                        // the item appeared in or vanished from the filtered sequence
+                        diffs.push(SeqDiff::Shift { at: i, by: if np { 1 } else { -1 } });
+                    } else if np {
+                        match diffs.last_mut() {
+                            Some(SeqDiff::Range(changed)) if changed.end == i => changed.end += 1,
+                            _ => diffs.push(SeqDiff::Range(i..i + 1)),
+                        }
+                    }
+                }
+                self.cast.notify_each(diffs);
+            }
+            SeqDiff::Shift { at, by } if *by > 0 => {
+                let flags: Vec<bool> = (*at..at + *by as usize).map(|idx| self.eval_pred(idx)).collect();
+                let count = flags.iter().filter(|f| **f).count();
+                self.old_preds.insert(*at, flags);
+                if count > 0 {
+                    let i = self.old_preds.rank(*at);
+                    self.cast.notify(&SeqDiff::Shift { at: i, by: count as isize });
+                }
+            }
+            SeqDiff::Shift { at, by } => {
+                let count = self.old_preds.remove(*at..at + by.unsigned_abs());
+                if count > 0 {
+                    let i = self.old_preds.rank(*at);
+                    self.cast.notify(&SeqDiff::Shift { at: i, by: -(count as isize) });
                 }
-                i += 1;
             }
-
-            offset
-        } else {
-            0
         }
     }
 }
@@ -79,7 +129,7 @@ where
     SrcView: SequenceView + ?Sized + 'static,
     P: Fn(&SrcView::Item) -> bool + Send + Sync + 'static,
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<SrcView, P> SequenceView for Filter<SrcView, P>
@@ -90,73 +140,42 @@ where
     type Item = SrcView::Item;
 
     fn len(&self) -> Option<usize> {
-        if let Some(src_len) = self.src_view.len() {
-            Some(src_len - self.get_offset(src_len))
-        } else {
-            None
-        }
+        self.src.update();
+        Some(self.state.read().unwrap().old_preds.count())
     }
 
     fn get(&self, idx: &usize) -> Option<Self::Item> {
-        self.src_view.get(&(idx + self.get_offset(*idx)))
+        self.src.update();
+        let state = self.state.read().unwrap();
+        state.src_view.get(&state.old_preds.select(*idx)?)
     }
 }
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
-impl<SrcView, P> Observer<SrcView> for Filter<SrcView, P>
+impl<SrcView, P> Observer<SrcView> for FilterState<SrcView, P>
 where
     SrcView: SequenceView + ?Sized + 'static,
     P: Fn(&SrcView::Item) -> bool + Send + Sync + 'static,
 {
     fn reset(&mut self, new_src: Option<Arc<SrcView>>) {
-        let old_len = self.len();
+        let old_len = self.old_preds.count();
         self.src_view = new_src;
-        self.old_preds = RwLock::new(Vec::new());
-        let new_len = self.len();
-
-        if let Some(len) = old_len {
-            self.cast.notify_each(0..len);
-        }
-        if let Some(len) = new_len {
-            self.cast.notify_each(0..len);
-        }
-    }
-
-    fn notify(&mut self, idx: &usize) {
-        let l = self.len().unwrap_or(0) + 1;
-        let np = if let Some(x) = self.src_view.get(idx) {
-            (self.pred)(&x)
-        } else {
-            false
-        };
-
-        let mut opds = self.old_preds.write().unwrap();
 
-        opds.resize_with(1 + *idx, || false);
-        let op = opds.get(*idx).cloned().unwrap_or(false);
-        *opds.get_mut(*idx).unwrap() = np;
+        let src_len = self.src_view.len().unwrap_or(0);
+        self.old_preds = FlagTree::from_flags((0..src_len).map(|idx| self.eval_pred(idx)));
+        let new_len = self.old_preds.count();
 
-        drop(opds);
+        self.cast.notify_each(SeqDiff::replace(old_len, new_len));
+    }
 
-        let i = (0..*idx)
-            .map(|j| {
-                if let Some(x) = self.src_view.get(&j) {
-                    if (self.pred)(&x) {
-                        1
-                    } else {
-                        0
-                    }
-                } else {
-                    0
-                }
-            })
-            .sum();
+    fn notify(&mut self, diff: &SeqDiff) {
+        self.pending.push(diff.clone());
+    }
 
-        if np != op {
-            self.cast.notify_each(i..l);
-        } else {
-            self.cast.notify(&i);
+    fn end_update(&mut self) {
+        for diff in std::mem::take(&mut self.pending) {
+            self.apply(&diff);
         }
     }
 }
@@ -208,6 +227,54 @@ mod tests {
         assert_eq!(target_view.get(&1), Some(13));
         assert_eq!(target_view.get(&2), Some(19));
     }
-}
 
+    #[test]
+    fn filter_seq_without_update() {
+        // reading the view delivers the pending source messages
+        let mut buffer = VecBuffer::new();
+        let target_view = buffer.get_port().to_sequence().map(|x| x + 10).filter(|x| *x > 10).get_view();
+
+        buffer.push(3);
+        buffer.push(-9);
+        buffer.push(1);
+        assert_eq!(target_view.len(), Some(2));
+        assert_eq!(target_view.get(&1), Some(11));
+
+        buffer.remove(0);
+        assert_eq!(target_view.get(&0), Some(11));
+        assert_eq!(target_view.len(), Some(1));
+    }
+
+    #[test]
+    fn filter_seq_update() {
+        let mut buffer = VecBuffer::with_data((0..100).collect());
+        let target_port = buffer.get_port().to_sequence().filter(|x| x % 3 == 0);
+        let target_view = target_port.get_view();
+
+        let check = |buffer: &VecBuffer<i32>| {
+            let data = buffer.get_port().get_view().unwrap();
+            let expected: Vec<i32> = data.read().unwrap().iter().cloned().filter(|x| x % 3 == 0).collect();
+            assert_eq!(target_view.len(), Some(expected.len()));
+            for (i, x) in expected.iter().enumerate() {
+                assert_eq!(target_view.get(&i), Some(*x));
+            }
+            assert_eq!(target_view.get(&expected.len()), None);
+        };
 
+        target_port.0.update();
+        check(&buffer);
+
+        buffer.update(3, 1);
+        buffer.update(4, 6);
+        buffer.remove(0);
+        buffer.insert(50, 999);
+        buffer.push(300);
+
+        target_port.0.update();
+        check(&buffer);
+
+        buffer.clear();
+        target_port.0.update();
+        check(&buffer);
+    }
+}
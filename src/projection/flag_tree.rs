@@ -0,0 +1,116 @@
+use {
+    crate::projection::order_tree::OrderTree,
+    std::{iter, ops::Range},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Sequence of flags with logarithmic rank, select, insertion and removal,
+/// used to translate indices between a sequence and its filtered subsequence.
+pub(crate) struct FlagTree {
+    tree: OrderTree<bool>,
+}
+
+impl Default for FlagTree {
+    fn default() -> Self {
+        FlagTree {
+            tree: OrderTree::new(|flag| *flag as usize),
+        }
+    }
+}
+
+impl FlagTree {
+    pub(crate) fn new() -> Self {
+        FlagTree::default()
+    }
+
+    pub(crate) fn from_flags(flags: impl IntoIterator<Item = bool>) -> Self {
+        let mut ft = FlagTree::new();
+        ft.tree.insert_iter(0, flags);
+        ft
+    }
+
+    /// unset flags up to `len`
+    fn pad(&mut self, len: usize) {
+        let n = self.tree.len();
+        if len > n {
+            self.tree.insert_iter(n, iter::repeat_n(false, len - n));
+        }
+    }
+
+    /// returns the previous flag at `idx`
+    pub(crate) fn set(&mut self, idx: usize, flag: bool) -> bool {
+        if idx >= self.tree.len() {
+            if !flag {
+                return false;
+            }
+            self.pad(idx + 1);
+        }
+
+        let handle = self.tree.at(idx).unwrap();
+        self.tree.update(handle, |f| std::mem::replace(f, flag))
+    }
+
+    /// inserts `flags` before `idx`, shifting all following flags
+    pub(crate) fn insert(&mut self, idx: usize, flags: impl IntoIterator<Item = bool>) {
+        self.pad(idx);
+        self.tree.insert_iter(idx, flags);
+    }
+
+    /// removes the flags in `range`, returns how many of them were set
+    pub(crate) fn remove(&mut self, range: Range<usize>) -> usize {
+        self.tree.remove_range(range)
+    }
+
+    /// number of set flags before `idx`
+    pub(crate) fn rank(&self, idx: usize) -> usize {
+        self.tree.weight_before(idx)
+    }
+
+    /// total number of set flags
+    pub(crate) fn count(&self) -> usize {
+        self.tree.total()
+    }
+
+    /// position of the set flag with rank `k`
+    pub(crate) fn select(&self, k: usize) -> Option<usize> {
+        self.tree.select_weight(k)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::projection::flag_tree::*;
+
+    #[test]
+    fn flag_tree_rank_select() {
+        let flags = vec![false, true, true, false, true, false, false, true];
+        let mut ft = FlagTree::from_flags(flags.clone());
+
+        for idx in 0..=flags.len() {
+            assert_eq!(ft.rank(idx), flags[..idx].iter().filter(|f| **f).count());
+        }
+        assert_eq!(ft.count(), 4);
+        assert_eq!(ft.select(0), Some(1));
+        assert_eq!(ft.select(3), Some(7));
+        assert_eq!(ft.select(4), None);
+
+        assert!(ft.set(2, false));
+        assert!(!ft.set(20, true));
+        assert_eq!(ft.count(), 4);
+        assert_eq!(ft.rank(20), 3);
+        assert_eq!(ft.select(2), Some(7));
+        assert_eq!(ft.select(3), Some(20));
+        assert!(!ft.set(19, false));
+        assert!(ft.set(20, true));
+
+        ft.insert(1, vec![true, false]);
+        assert_eq!(ft.select(0), Some(1));
+        assert_eq!(ft.select(1), Some(3));
+        assert_eq!(ft.remove(0..3), 1);
+        assert_eq!(ft.count(), 4);
+        assert_eq!(ft.select(0), Some(0));
+    }
+}
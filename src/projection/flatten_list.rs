@@ -0,0 +1,236 @@
+use {
+    crate::{
+        view::{
+            channel::queue_channel, InnerViewPort, Observer, ObserverBroadcast, ObserverExt,
+            OuterViewPort, View, ViewPort,
+            list::{ListView, ListDiff}
+        },
+        projection::projection_helper::ProjectionHelper,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item> OuterViewPort<dyn ListView<OuterViewPort<dyn ListView<Item>>>>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    pub fn flatten(&self) -> OuterViewPort<dyn ListView<Item>> {
+        let port = ViewPort::new();
+        FlattenList::new(self.clone(), port.inner());
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct Chunk<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    /// key of the projection argument, stays the same when the chunk moves
+    id: usize,
+    len: usize,
+    view: Arc<RwLock<Option<Arc<dyn ListView<Item>>>>>,
+}
+
+pub struct FlattenList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    /// chunks in the order of the top list
+    chunks: Vec<Chunk<Item>>,
+    next_id: usize,
+    cast: Arc<RwLock<ObserverBroadcast<dyn ListView<Item>>>>,
+    proj_helper: ProjectionHelper<usize, Self>,
+}
+
+impl<Item> View for FlattenList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    type Msg = ListDiff<Item>;
+}
+
+impl<Item> ListView<Item> for FlattenList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    fn len(&self) -> Option<usize> {
+        Some(self.chunks.iter().map(|chunk| chunk.len).sum())
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        let mut offset = 0;
+        for chunk in self.chunks.iter() {
+            if *idx < offset + chunk.len {
+                return chunk.view.get(&(*idx - offset));
+            }
+            offset += chunk.len;
+        }
+        None
+    }
+}
+
+impl<Item> FlattenList<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    pub fn new(
+        top_port: OuterViewPort<dyn ListView<OuterViewPort<dyn ListView<Item>>>>,
+        out_port: InnerViewPort<dyn ListView<Item>>,
+    ) -> Arc<RwLock<Self>> {
+        let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
+        proj_helper.new_list_arg(usize::MAX, top_port, |s: &mut Self, diff| {
+            s.notify_top(diff);
+        });
+
+        let flat = Arc::new(RwLock::new(FlattenList {
+            chunks: Vec::new(),
+            next_id: 0,
+            cast: out_port.get_broadcast(),
+            proj_helper,
+        }));
+
+        flat.write().unwrap().proj_helper.set_proj(&flat);
+        out_port.set_view(Some(flat.clone()));
+        flat
+    }
+
+    /// position of the chunk `id` in the top list and its offset in the flattened list
+    fn find_chunk(&self, id: usize) -> Option<(usize, usize)> {
+        let mut offset = 0;
+        for (chunk_idx, chunk) in self.chunks.iter().enumerate() {
+            if chunk.id == id {
+                return Some((chunk_idx, offset));
+            }
+            offset += chunk.len;
+        }
+        None
+    }
+
+    fn offset(&self, chunk_idx: usize) -> usize {
+        self.chunks[..chunk_idx].iter().map(|chunk| chunk.len).sum()
+    }
+
+    fn notify_top(&mut self, diff: &ListDiff<OuterViewPort<dyn ListView<Item>>>) {
+        match diff {
+            ListDiff::Clear => {
+                for chunk in std::mem::take(&mut self.chunks) {
+                    self.proj_helper.remove_arg(&chunk.id);
+                }
+                self.cast.notify(&ListDiff::Clear);
+            }
+            ListDiff::Remove(chunk_idx) => {
+                self.remove_chunk(*chunk_idx);
+            }
+            ListDiff::Insert{ idx: chunk_idx, val: chunk_port } => {
+                self.insert_chunk(*chunk_idx, chunk_port.clone());
+            }
+            ListDiff::Update{ idx: chunk_idx, val: chunk_port } => {
+                self.remove_chunk(*chunk_idx);
+                self.insert_chunk(*chunk_idx, chunk_port.clone());
+            }
+        }
+    }
+
+    fn remove_chunk(&mut self, chunk_idx: usize) {
+        let offset = self.offset(chunk_idx);
+        let chunk = self.chunks.remove(chunk_idx);
+        self.proj_helper.remove_arg(&chunk.id);
+        self.cast.notify_each((0..chunk.len).map(|_| ListDiff::Remove(offset)));
+    }
+
+    /// create a new observer for the sub list
+    /// and insert its current items right away
+    fn insert_chunk(&mut self, chunk_idx: usize, chunk_port: OuterViewPort<dyn ListView<Item>>) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let arg = self.proj_helper.new_arg(
+            id,
+            Arc::new(chunk_port.0.clone()),
+            move |s: &mut Self, diff| {
+                s.notify_chunk(id, diff);
+            },
+            queue_channel(),
+        );
+
+        self.chunks.insert(
+            chunk_idx,
+            Chunk {
+                id,
+                len: 0,
+                view: chunk_port.add_observer(arg.clone()),
+            },
+        );
+
+        // the projection is already locked, so the snapshot
+        // sent by reset() can not wait for the next update
+        arg.read().unwrap().flush(self);
+    }
+
+    fn notify_chunk(&mut self, id: usize, diff: &ListDiff<Item>) {
+        // messages from chunks which were removed in the meantime are dropped
+        if let Some((chunk_idx, offset)) = self.find_chunk(id) {
+            let chunk = &mut self.chunks[chunk_idx];
+            match diff {
+                ListDiff::Clear => {
+                    let len = std::mem::replace(&mut chunk.len, 0);
+                    self.cast.notify_each((0..len).map(|_| ListDiff::Remove(offset)));
+                }
+                ListDiff::Remove(idx) => {
+                    chunk.len -= 1;
+                    self.cast.notify(&ListDiff::Remove(offset + idx));
+                }
+                ListDiff::Insert{ idx, val } => {
+                    chunk.len += 1;
+                    self.cast.notify(&ListDiff::Insert{ idx: offset + idx, val: val.clone() });
+                }
+                ListDiff::Update{ idx, val } => {
+                    self.cast.notify(&ListDiff::Update{ idx: offset + idx, val: val.clone() });
+                }
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::port::UpdateTask;
+
+    #[test]
+    fn flatten_list1() {
+        let mut a = VecBuffer::with_data(vec![1, 2]);
+        let mut b = VecBuffer::with_data(vec![10]);
+        let c = VecBuffer::with_data(vec![100, 200]);
+
+        let mut top = VecBuffer::with_data(vec![
+            a.get_port().to_list(),
+            b.get_port().to_list(),
+        ]);
+        let target_port = top.get_port().to_list().flatten();
+
+        let mirror = VecBuffer::<i32>::new();
+        let _keepalive = mirror.attach_to(target_port.clone());
+        assert_eq!(mirror.len(), 3);
+
+        b.push(11);
+        a.remove(0);
+        top.insert(1, c.get_port().to_list());
+        b.update(0, 20);
+        top.remove(0);
+        a.push(3); // no longer part of the flattened list
+
+        target_port.0.update();
+        mirror.get_port().0.update();
+
+        assert_eq!(*mirror.get_port().get_view().unwrap().read().unwrap(), vec![100, 200, 20, 11]);
+        assert_eq!(target_port.get_view().unwrap().get(&2), Some(20));
+    }
+}
@@ -1,14 +1,14 @@
 use {
     crate::{
         view::{
-            port::UpdateTask, InnerViewPort, Observer, ObserverBroadcast, ObserverExt,
+            channel::channel, InnerViewPort, Observer, ObserverBroadcast,
             OuterViewPort, View, ViewPort,
-            sequence::SequenceView,
+            sequence::{SeqDiff, SeqDiffBatch, SequenceView},
         },
         projection::projection_helper::ProjectionHelper,
     },
     std::sync::RwLock,
-    std::{collections::BTreeMap, sync::Arc},
+    std::sync::Arc,
 };
 
 impl<Item> OuterViewPort<dyn SequenceView<Item = OuterViewPort<dyn SequenceView<Item = Item>>>>
@@ -26,18 +26,21 @@ pub struct Chunk<Item>
 where
     Item: 'static,
 {
-    offset: usize,
+    /// key of the projection argument, stays the same when the chunk moves
+    id: usize,
     len: usize,
-    view: Arc<dyn SequenceView<Item = Item>>,
+    view: Arc<RwLock<Option<Arc<dyn SequenceView<Item = Item>>>>>,
 }
 
 pub struct Flatten<Item>
 where
     Item: 'static,
 {
-    length: usize,
     top: Arc<dyn SequenceView<Item = OuterViewPort<dyn SequenceView<Item = Item>>>>,
-    chunks: BTreeMap<usize, Chunk<Item>>,
+
+    /// chunks in the order of the top sequence
+    chunks: Vec<Chunk<Item>>,
+    next_id: usize,
     cast: Arc<RwLock<ObserverBroadcast<dyn SequenceView<Item = Item>>>>,
     proj_helper: ProjectionHelper<usize, Self>,
 }
@@ -46,7 +49,7 @@ impl<Item> View for Flatten<Item>
 where
     Item: 'static,
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<Item> SequenceView for Flatten<Item>
@@ -56,12 +59,18 @@ where
     type Item = Item;
 
     fn get(&self, idx: &usize) -> Option<Self::Item> {
-        let chunk = self.chunks.get(&self.get_chunk_idx(*idx)?)?;
-        chunk.view.get(&(*idx - chunk.offset))
+        let mut offset = 0;
+        for chunk in self.chunks.iter() {
+            if *idx < offset + chunk.len {
+                return chunk.view.get(&(*idx - offset));
+            }
+            offset += chunk.len;
+        }
+        None
     }
 
     fn len(&self) -> Option<usize> {
-        Some(self.length)
+        Some(self.chunks.iter().map(|chunk| chunk.len).sum())
     }
 }
 
@@ -78,11 +87,11 @@ where
         let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
 
         let flat = Arc::new(RwLock::new(Flatten {
-            length: 0,
-            top: proj_helper.new_sequence_arg(usize::MAX, top_port, |s: &mut Self, chunk_idx| {
-                s.update_chunk(*chunk_idx);
+            top: proj_helper.new_sequence_arg(usize::MAX, top_port, |s: &mut Self, diff| {
+                s.notify_top(diff);
             }),
-            chunks: BTreeMap::new(),
+            chunks: Vec::new(),
+            next_id: 0,
             cast: out_port.get_broadcast(),
             proj_helper,
         }));
@@ -92,93 +101,95 @@ where
         flat
     }
 
-    /// the top-sequence has changed the item at chunk_idx,
-    /// create a new observer for the contained sub sequence
-    fn update_chunk(&mut self, chunk_idx: usize) {
-        if let Some(chunk_port) = self.top.get(&chunk_idx) {
-            self.chunks.insert(
-                chunk_idx,
-                Chunk {
-                    offset: 0, // will be adjusted by update_offsets() later
-                    len: 0,
-                    view: self.proj_helper.new_sequence_arg(
-                        chunk_idx,
-                        chunk_port.clone(),
-                        move |s: &mut Self, idx| {
-                            if let Some(chunk) = s.chunks.get(&chunk_idx) {
-                                let chunk_offset = chunk.offset;
-                                let chunk_len = chunk.view.len().unwrap_or(0);
-
-                                let mut dirty_idx = Vec::new();
-                                if chunk.len != chunk_len {
-                                    dirty_idx = s.update_all_offsets();
-                                }
-
-                                s.cast.notify(&(idx + chunk_offset));
-                                s.cast.notify_each(dirty_idx);
-                            } else {
-                                let dirty_idx = s.update_all_offsets();
-                                s.cast.notify_each(dirty_idx);
-                            }
-                        },
-                    ),
-                },
-            );
-
-            chunk_port.0.update();
-            let dirty_idx = self.update_all_offsets();
-            self.cast.notify_each(dirty_idx);
-        } else {
-            // todo:
-            self.proj_helper.remove_arg(&chunk_idx);
-
-            self.chunks.remove(&chunk_idx);
-
-            let dirty_idx = self.update_all_offsets();
-            self.cast.notify_each(dirty_idx);
+    /// position of the chunk `id` in the top sequence and its offset in the flattened sequence
+    fn find_chunk(&self, id: usize) -> Option<(usize, usize)> {
+        let mut offset = 0;
+        for (chunk_idx, chunk) in self.chunks.iter().enumerate() {
+            if chunk.id == id {
+                return Some((chunk_idx, offset));
+            }
+            offset += chunk.len;
         }
+        None
     }
 
-    /// recalculate all chunk offsets beginning at start_idx
-    /// and update length of flattened sequence
-    fn update_all_offsets(&mut self) -> Vec<usize> {
-        let mut dirty_idx = Vec::new();
-        let mut cur_offset = 0;
-
-        for (_chunk_idx, chunk) in self.chunks.iter_mut() {
-            let old_offset = chunk.offset;
-            chunk.offset = cur_offset;
-            chunk.len = chunk.view.len().unwrap_or(0);
-
-            if old_offset != cur_offset {
-                dirty_idx.extend(
-                    std::cmp::min(old_offset, cur_offset)
-                        ..std::cmp::max(old_offset, cur_offset) + chunk.len,
-                );
+    fn offset(&self, chunk_idx: usize) -> usize {
+        self.chunks[..chunk_idx].iter().map(|chunk| chunk.len).sum()
+    }
+
+    fn notify_top(&mut self, diff: &SeqDiff) {
+        match diff {
+            SeqDiff::Range(r) => {
+                for chunk_idx in r.start..std::cmp::min(r.end, self.chunks.len()) {
+                    self.remove_chunk(chunk_idx);
+                    self.insert_chunk(chunk_idx);
+                }
+            }
+            SeqDiff::Shift { at, by } if *by > 0 => {
+                for chunk_idx in *at..at + *by as usize {
+                    self.insert_chunk(chunk_idx);
+                }
             }
+            SeqDiff::Shift { at, by } => {
+                for _ in 0..by.unsigned_abs() {
+                    self.remove_chunk(*at);
+                }
+            }
+        }
+    }
 
-            cur_offset += chunk.len;
+    fn remove_chunk(&mut self, chunk_idx: usize) {
+        let offset = self.offset(chunk_idx);
+        let chunk = self.chunks.remove(chunk_idx);
+        self.proj_helper.remove_arg(&chunk.id);
+        if chunk.len > 0 {
+            self.cast.notify(&SeqDiff::Shift { at: offset, by: -(chunk.len as isize) });
         }
+    }
 
-        let old_length = self.length;
-        self.length = cur_offset;
+    /// the top sequence has a new item at chunk_idx,
+    /// create a new observer for the contained sub sequence
+    /// and insert its current items right away
+    fn insert_chunk(&mut self, chunk_idx: usize) {
+        let chunk_port = self.top.get(&chunk_idx).unwrap_or_default();
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let arg = self.proj_helper.new_arg(
+            id,
+            Arc::new(chunk_port.0.clone()),
+            move |s: &mut Self, diff| {
+                s.notify_chunk(id, diff);
+            },
+            channel::<SeqDiffBatch>(),
+        );
 
-        dirty_idx.extend(self.length..old_length);
+        self.chunks.insert(
+            chunk_idx,
+            Chunk {
+                id,
+                len: 0,
+                view: chunk_port.add_observer(arg.clone()),
+            },
+        );
 
-        dirty_idx
+        // the projection is already locked, so the snapshot
+        // sent by reset() can not wait for the next update
+        arg.read().unwrap().flush(self);
     }
 
-    /// given an index in the flattened sequence,
-    /// which sub-sequence does it belong to?
-    fn get_chunk_idx(&self, glob_idx: usize) -> Option<usize> {
-        let mut offset = 0;
-        for (chunk_idx, chunk) in self.chunks.iter() {
-            offset += chunk.view.len().unwrap_or(0);
-            if glob_idx < offset {
-                return Some(*chunk_idx);
-            }
+    fn notify_chunk(&mut self, id: usize, diff: &SeqDiff) {
+        // messages from chunks which were removed in the meantime are dropped
+        if let Some((chunk_idx, offset)) = self.find_chunk(id) {
+            let chunk = &mut self.chunks[chunk_idx];
+            chunk.len = diff.apply_len(chunk.len);
+
+            self.cast.notify(&match diff {
+                SeqDiff::Range(r) => SeqDiff::Range(offset + r.start..offset + r.end),
+                SeqDiff::Shift { at, by } => SeqDiff::Shift { at: offset + at, by: *by },
+            });
         }
-        None
     }
 }
 
@@ -0,0 +1,344 @@
+use {
+    crate::{
+        projection::order_tree::OrderTree,
+        view::{
+            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            index::{IndexArea, IndexView},
+            list::{ListDiff, ListView, ListViewExt},
+        },
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::{collections::HashMap, hash::Hash},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> OuterViewPort<dyn ListView<Item>> {
+    /// index of all groups of items with the same key,
+    /// every group is a sublist which keeps the source order.
+    /// A group which becomes empty is hidden from the index but kept,
+    /// so it continues to get updates once the key reappears.
+    pub fn group_by<Key, F>(
+        &self,
+        key_fn: F,
+    ) -> OuterViewPort<dyn IndexView<Key, Item = OuterViewPort<dyn ListView<Item>>>>
+    where
+        Key: Clone + Hash + Eq + Send + Sync + 'static,
+        F: Fn(&Item) -> Key + Send + Sync + 'static,
+    {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let group_by = Arc::new(RwLock::new(GroupList {
+            src_port: self.clone(),
+            key_fn,
+            slots: OrderTree::new(|_| 0),
+            ids: HashMap::new(),
+            groups: Vec::new(),
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(group_by.clone());
+        port.inner().set_view(Some(group_by));
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Sublist of all items with the same key
+pub struct ListGroup<Item: Clone + Send + Sync + 'static> {
+    /// every member along with its slot, in source order
+    items: OrderTree<(Item, usize)>,
+    cast: Arc<RwLock<ObserverBroadcast<dyn ListView<Item>>>>,
+}
+
+impl<Item: Clone + Send + Sync + 'static> View for ListGroup<Item> {
+    type Msg = ListDiff<Item>;
+}
+
+impl<Item: Clone + Send + Sync + 'static> ListView<Item> for ListGroup<Item> {
+    fn len(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        Some(self.items.get(self.items.at(*idx)?).0.clone())
+    }
+}
+
+/// group of a source item and its node in the items of that group
+#[derive(Clone, Copy)]
+struct Slot {
+    group: usize,
+    member: usize,
+}
+
+struct Group<Key, Item: Clone + Send + Sync + 'static> {
+    key: Key,
+    view: Arc<RwLock<ListGroup<Item>>>,
+    port: OuterViewPort<dyn ListView<Item>>,
+}
+
+impl<Key, Item: Clone + Send + Sync + 'static> Group<Key, Item> {
+    fn is_empty(&self) -> bool {
+        self.view.read().unwrap().items.len() == 0
+    }
+}
+
+type GroupCast<Key, Item> = Arc<RwLock<ObserverBroadcast<dyn IndexView<Key, Item = OuterViewPort<dyn ListView<Item>>>>>>;
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct GroupList<Item, Key, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    src_port: OuterViewPort<dyn ListView<Item>>,
+    key_fn: F,
+
+    /// one slot for every source index, source positions are
+    /// only stored here, so a shift does not touch any group
+    slots: OrderTree<Slot>,
+
+    /// groups are never removed, `ids` refers to their index in `groups`
+    ids: HashMap<Key, usize>,
+    groups: Vec<Group<Key, Item>>,
+    cast: GroupCast<Key, Item>,
+}
+
+impl<Item, Key, F> GroupList<Item, Key, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    /// group of `key`, which is created if it does not exist yet
+    fn group_id(&mut self, key: Key) -> usize {
+        if let Some(id) = self.ids.get(&key) {
+            return *id;
+        }
+
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.src_port.0.clone()));
+
+        let view = Arc::new(RwLock::new(ListGroup {
+            items: OrderTree::new(|_| 0),
+            cast: port.inner().get_broadcast(),
+        }));
+        port.inner().set_view(Some(view.clone()));
+
+        let id = self.groups.len();
+        self.ids.insert(key.clone(), id);
+        self.groups.push(Group { key, view, port: port.into_outer() });
+        id
+    }
+
+    /// add the source item `val` of `slot` to the group `id`
+    fn add_member(&mut self, slot: usize, id: usize, val: Item) {
+        let group = &self.groups[id];
+        let mut view = group.view.write().unwrap();
+
+        let idx = self.slots.position(slot);
+        let pos = view.items.partition_point(|m| self.slots.position(view.items.get(m).1) < idx);
+        if view.items.len() == 0 {
+            self.cast.notify(&IndexArea::Set(vec![group.key.clone()]));
+        }
+
+        let member = view.items.insert(pos, (val.clone(), slot));
+        view.cast.notify(&ListDiff::Insert{ idx: pos, val });
+        self.slots.update(slot, |s| *s = Slot { group: id, member });
+    }
+
+    /// remove the source item of `slot` from its group
+    fn remove_member(&mut self, slot: usize) {
+        let Slot { group: id, member } = *self.slots.get(slot);
+        let group = &self.groups[id];
+        let mut view = group.view.write().unwrap();
+
+        let pos = view.items.position(member);
+        view.items.remove(member);
+        view.cast.notify(&ListDiff::Remove(pos));
+
+        if view.items.len() == 0 {
+            self.cast.notify(&IndexArea::Set(vec![group.key.clone()]));
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item, Key, F> View for GroupList<Item, Key, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    type Msg = IndexArea<Key>;
+}
+
+impl<Item, Key, F> IndexView<Key> for GroupList<Item, Key, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    type Item = OuterViewPort<dyn ListView<Item>>;
+
+    fn get(&self, key: &Key) -> Option<Self::Item> {
+        let group = &self.groups[*self.ids.get(key)?];
+        if group.is_empty() {
+            None
+        } else {
+            Some(group.port.clone())
+        }
+    }
+
+    fn area(&self) -> IndexArea<Key> {
+        IndexArea::Set(
+            self.groups.iter()
+                .filter(|group| !group.is_empty())
+                .map(|group| group.key.clone())
+                .collect(),
+        )
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item, Key, F> Observer<dyn ListView<Item>> for GroupList<Item, Key, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    fn reset(&mut self, view: Option<Arc<dyn ListView<Item>>>) {
+        self.notify(&ListDiff::Clear);
+        if let Some(v) = view {
+            for val in v.iter() {
+                self.notify(&ListDiff::Insert{ idx: self.slots.len(), val });
+            }
+        }
+    }
+
+    fn notify(&mut self, diff: &ListDiff<Item>) {
+        match diff {
+            ListDiff::Clear => {
+                self.slots.clear();
+                let mut old_keys = Vec::new();
+                for group in self.groups.iter() {
+                    let mut view = group.view.write().unwrap();
+                    if view.items.len() > 0 {
+                        old_keys.push(group.key.clone());
+                        view.items.clear();
+                        view.cast.notify(&ListDiff::Clear);
+                    }
+                }
+                self.cast.notify(&IndexArea::Set(old_keys));
+            }
+            ListDiff::Remove(idx) => {
+                if let Some(slot) = self.slots.at(*idx) {
+                    self.remove_member(slot);
+                    self.slots.remove(slot);
+                }
+            }
+            ListDiff::Insert{ idx, val } => {
+                let id = self.group_id((self.key_fn)(val));
+                let slot = self.slots.insert(*idx, Slot { group: id, member: 0 });
+                self.add_member(slot, id, val.clone());
+            }
+            ListDiff::Update{ idx, val } => {
+                let Some(slot) = self.slots.at(*idx) else {
+                    return;
+                };
+                let id = self.group_id((self.key_fn)(val));
+                let Slot { group, member } = *self.slots.get(slot);
+                if group == id {
+                    let mut view = self.groups[id].view.write().unwrap();
+                    let pos = view.items.position(member);
+                    view.items.update(member, |(v, _)| *v = val.clone());
+                    view.cast.notify(&ListDiff::Update{ idx: pos, val: val.clone() });
+                } else {
+                    self.remove_member(slot);
+                    self.add_member(slot, id, val.clone());
+                }
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::{port::UpdateTask, index::IndexView, list::ListViewExt};
+
+    #[test]
+    fn group_list1() {
+        let mut buffer = VecBuffer::with_data(vec!["apple", "avocado", "banana"]);
+        let groups = buffer.get_port().to_list().group_by(|s| s.chars().next().unwrap());
+        let groups_view = groups.get_view().unwrap();
+
+        let a = VecBuffer::<&str>::new();
+        let _keepalive = a.attach_to(groups_view.get(&'a').unwrap());
+        assert_eq!(a.len(), 2);
+
+        buffer.insert(1, "apricot");
+        buffer.update(0, "blueberry"); // moves into the 'b' group
+        buffer.push("cherry");         // new group
+        groups.0.update();
+        a.get_port().0.update();
+
+        assert_eq!(*a.get_port().get_view().unwrap().read().unwrap(), vec!["apricot", "avocado"]);
+
+        let b = groups_view.get(&'b').unwrap();
+        assert_eq!(b.get_view().unwrap().get(&0), Some("blueberry"));
+        assert_eq!(b.get_view().unwrap().get(&1), Some("banana"));
+        assert!(groups_view.get(&'c').is_some());
+
+        buffer.remove(4);
+        groups.0.update();
+        assert!(groups_view.get(&'c').is_none());
+
+        // an empty group is kept and gets updates once its key reappears
+        buffer.remove(1);
+        buffer.remove(1);
+        groups.0.update();
+        a.get_port().0.update();
+        assert!(groups_view.get(&'a').is_none());
+        assert_eq!(a.len(), 0);
+
+        buffer.push("almond");
+        groups.0.update();
+        a.get_port().0.update();
+        assert_eq!(*a.get_port().get_view().unwrap().read().unwrap(), vec!["almond"]);
+    }
+
+    #[test]
+    fn group_list_shift() {
+        let mut buffer = VecBuffer::with_data((0..20).collect::<Vec<usize>>());
+        let groups = buffer.get_port().to_list().group_by(|x| x % 3);
+        let groups_view = groups.get_view().unwrap();
+
+        buffer.insert(0, 30);
+        buffer.insert(7, 31);
+        buffer.remove(12);
+        buffer.remove(3);
+        buffer.update(5, 32);
+        buffer.push(33);
+        groups.0.update();
+
+        let data = buffer.get_port().get_view().unwrap().read().unwrap().clone();
+        for key in 0..3 {
+            let group = groups_view.get(&key).unwrap().get_view().unwrap();
+            assert_eq!(
+                group.iter().collect::<Vec<_>>(),
+                data.iter().cloned().filter(|x| x % 3 == key).collect::<Vec<_>>()
+            );
+        }
+    }
+}
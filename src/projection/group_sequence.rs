@@ -0,0 +1,403 @@
+use {
+    crate::{
+        projection::order_tree::OrderTree,
+        view::{
+            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            index::{IndexArea, IndexView},
+            sequence::{SeqDiff, SeqDiffBatch, SequenceView},
+        },
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::{collections::HashMap, hash::Hash},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: 'static> OuterViewPort<dyn SequenceView<Item = Item>> {
+    /// index of all groups of items with the same key,
+    /// every group is a subsequence which keeps the source order.
+    /// A group which becomes empty is hidden from the index but kept,
+    /// so it continues to get updates once the key reappears.
+    pub fn group_by<Key, F>(
+        &self,
+        key_fn: F,
+    ) -> OuterViewPort<dyn IndexView<Key, Item = OuterViewPort<dyn SequenceView<Item = Item>>>>
+    where
+        Key: Clone + Hash + Eq + Send + Sync + 'static,
+        F: Fn(&Item) -> Key + Send + Sync + 'static,
+    {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let group_by = Arc::new(RwLock::new(GroupSequence {
+            src_port: self.clone(),
+            src_view: None,
+            key_fn,
+            slots: Arc::new(RwLock::new(OrderTree::new(|_| 0))),
+            ids: HashMap::new(),
+            groups: Vec::new(),
+            pending: SeqDiffBatch::default(),
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(group_by.clone());
+        port.inner().set_view(Some(group_by));
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// group of a source item and its node in the members of that group
+#[derive(Clone, Copy)]
+struct Slot {
+    group: usize,
+    member: usize,
+}
+
+/// one slot for every source index
+type Slots = Arc<RwLock<OrderTree<Slot>>>;
+
+/// Subsequence of all items with the same key
+pub struct SequenceGroup<Item: 'static> {
+    src_view: Option<Arc<dyn SequenceView<Item = Item>>>,
+    slots: Slots,
+
+    /// slot of every member, in source order
+    members: OrderTree<usize>,
+    cast: Arc<RwLock<ObserverBroadcast<dyn SequenceView<Item = Item>>>>,
+}
+
+impl<Item: 'static> View for SequenceGroup<Item> {
+    type Msg = SeqDiff;
+}
+
+impl<Item: 'static> SequenceView for SequenceGroup<Item> {
+    type Item = Item;
+
+    fn len(&self) -> Option<usize> {
+        Some(self.members.len())
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        let slot = *self.members.get(self.members.at(*idx)?);
+        let src_idx = self.slots.read().unwrap().position(slot);
+        self.src_view.get(&src_idx)
+    }
+}
+
+struct Group<Key, Item: 'static> {
+    key: Key,
+    view: Arc<RwLock<SequenceGroup<Item>>>,
+    port: OuterViewPort<dyn SequenceView<Item = Item>>,
+}
+
+impl<Key, Item: 'static> Group<Key, Item> {
+    fn is_empty(&self) -> bool {
+        self.view.read().unwrap().members.len() == 0
+    }
+}
+
+type GroupCast<Key, Item> = Arc<RwLock<ObserverBroadcast<dyn IndexView<Key, Item = OuterViewPort<dyn SequenceView<Item = Item>>>>>>;
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct GroupSequence<Item, Key, F>
+where
+    Item: 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    src_port: OuterViewPort<dyn SequenceView<Item = Item>>,
+    src_view: Option<Arc<dyn SequenceView<Item = Item>>>,
+    key_fn: F,
+
+    /// source positions are only stored by the slots,
+    /// so a shift does not touch the members of any group
+    slots: Slots,
+
+    /// groups are never removed, `ids` refers to their index in `groups`
+    ids: HashMap<Key, usize>,
+    groups: Vec<Group<Key, Item>>,
+
+    /// messages of the running update, the keys
+    /// are only computed once the update is complete
+    pending: SeqDiffBatch,
+    cast: GroupCast<Key, Item>,
+}
+
+impl<Item, Key, F> GroupSequence<Item, Key, F>
+where
+    Item: 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    /// group of `key`, which is created if it does not exist yet
+    fn group_id(&mut self, key: Key) -> usize {
+        if let Some(id) = self.ids.get(&key) {
+            return *id;
+        }
+
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.src_port.0.clone()));
+
+        let view = Arc::new(RwLock::new(SequenceGroup {
+            src_view: self.src_view.clone(),
+            slots: self.slots.clone(),
+            members: OrderTree::new(|_| 0),
+            cast: port.inner().get_broadcast(),
+        }));
+        port.inner().set_view(Some(view.clone()));
+
+        let id = self.groups.len();
+        self.ids.insert(key.clone(), id);
+        self.groups.push(Group { key, view, port: port.into_outer() });
+        id
+    }
+
+    /// add the source item of `slot` to the group `id`
+    fn add_member(&mut self, slot: usize, id: usize) {
+        let group = &self.groups[id];
+        let mut view = group.view.write().unwrap();
+
+        let pos = {
+            let slots = self.slots.read().unwrap();
+            let idx = slots.position(slot);
+            view.members.partition_point(|m| slots.position(*view.members.get(m)) < idx)
+        };
+        let appeared = view.members.len() == 0;
+        let member = view.members.insert(pos, slot);
+        view.cast.notify(&SeqDiff::Shift { at: pos, by: 1 });
+        drop(view);
+
+        // the group lock is released first, since readers of a group lock the slots
+        self.slots.write().unwrap().update(slot, |s| *s = Slot { group: id, member });
+        if appeared {
+            self.cast.notify(&IndexArea::Set(vec![group.key.clone()]));
+        }
+    }
+
+    /// remove the source item of `slot` from its group
+    fn remove_member(&mut self, slot: usize) {
+        let Slot { group: id, member } = *self.slots.read().unwrap().get(slot);
+        let group = &self.groups[id];
+        let mut view = group.view.write().unwrap();
+
+        let pos = view.members.position(member);
+        view.members.remove(member);
+        view.cast.notify(&SeqDiff::Shift { at: pos, by: -1 });
+
+        if view.members.len() == 0 {
+            self.cast.notify(&IndexArea::Set(vec![group.key.clone()]));
+        }
+    }
+
+    fn touch_member(&mut self, slot: usize) {
+        let Slot { group: id, member } = *self.slots.read().unwrap().get(slot);
+        let mut view = self.groups[id].view.write().unwrap();
+        let pos = view.members.position(member);
+        view.cast.notify(&SeqDiff::Range(pos..pos + 1));
+    }
+
+    fn apply(&mut self, diff: &SeqDiff) {
+        match diff {
+            SeqDiff::Range(r) => {
+                for idx in r.clone() {
+                    let slot = self.slots.read().unwrap().at(idx);
+                    if let (Some(slot), Some(x)) = (slot, self.src_view.get(&idx)) {
+                        let id = self.group_id((self.key_fn)(&x));
+                        if self.slots.read().unwrap().get(slot).group == id {
+                            self.touch_member(slot);
+                        } else {
+                            self.remove_member(slot);
+                            self.add_member(slot, id);
+                        }
+                    }
+                }
+            }
+            SeqDiff::Shift { at, by } if *by > 0 => {
+                let mut ids = Vec::new();
+                for idx in *at..at + *by as usize {
+                    if let Some(x) = self.src_view.get(&idx) {
+                        let key = (self.key_fn)(&x);
+                        ids.push(self.group_id(key));
+                    }
+                }
+
+                let slots = self.slots.write().unwrap()
+                    .insert_iter(*at, ids.iter().map(|&group| Slot { group, member: 0 }));
+                for (slot, id) in slots.into_iter().zip(ids) {
+                    self.add_member(slot, id);
+                }
+            }
+            SeqDiff::Shift { at, by } => {
+                let end = at + by.unsigned_abs();
+                for idx in (*at..end).rev() {
+                    let slot = self.slots.read().unwrap().at(idx);
+                    if let Some(slot) = slot {
+                        self.remove_member(slot);
+                    }
+                }
+                self.slots.write().unwrap().remove_range(*at..end);
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item, Key, F> View for GroupSequence<Item, Key, F>
+where
+    Item: 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    type Msg = IndexArea<Key>;
+}
+
+impl<Item, Key, F> IndexView<Key> for GroupSequence<Item, Key, F>
+where
+    Item: 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    type Item = OuterViewPort<dyn SequenceView<Item = Item>>;
+
+    fn get(&self, key: &Key) -> Option<Self::Item> {
+        let group = &self.groups[*self.ids.get(key)?];
+        if group.is_empty() {
+            None
+        } else {
+            Some(group.port.clone())
+        }
+    }
+
+    fn area(&self) -> IndexArea<Key> {
+        IndexArea::Set(
+            self.groups.iter()
+                .filter(|group| !group.is_empty())
+                .map(|group| group.key.clone())
+                .collect(),
+        )
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item, Key, F> Observer<dyn SequenceView<Item = Item>> for GroupSequence<Item, Key, F>
+where
+    Item: 'static,
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    F: Fn(&Item) -> Key + Send + Sync,
+{
+    fn reset(&mut self, view: Option<Arc<dyn SequenceView<Item = Item>>>) {
+        // all groups are emptied and refer to the new view
+        let mut old_keys = Vec::new();
+        for group in self.groups.iter() {
+            let mut group_view = group.view.write().unwrap();
+            let len = group_view.members.len();
+            if len > 0 {
+                old_keys.push(group.key.clone());
+                group_view.members.clear();
+                group_view.cast.notify(&SeqDiff::Shift { at: 0, by: -(len as isize) });
+            }
+            group_view.src_view = view.clone();
+        }
+        self.cast.notify(&IndexArea::Set(old_keys));
+
+        self.src_view = view;
+        self.slots.write().unwrap().clear();
+        let len = self.src_view.len().unwrap_or(0);
+        self.apply(&SeqDiff::Shift { at: 0, by: len as isize });
+    }
+
+    fn notify(&mut self, diff: &SeqDiff) {
+        self.pending.push(diff.clone());
+    }
+
+    fn end_update(&mut self) {
+        for diff in std::mem::take(&mut self.pending) {
+            self.apply(&diff);
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::{port::UpdateTask, index::IndexView, sequence::{SeqDiff, SequenceViewExt}};
+
+    #[test]
+    fn group_seq1() {
+        let mut buffer = VecBuffer::with_data(vec![1, 2, 3, 4, 5]);
+        let groups = buffer.get_port().to_sequence().group_by(|x| x % 2 == 0);
+        let groups_view = groups.get_view().unwrap();
+
+        let even = groups_view.get(&true).unwrap();
+        let even_view = even.get_view().unwrap();
+        assert_eq!(even_view.iter().collect::<Vec<_>>(), vec![2, 4]);
+
+        let notified = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _obs = even.add_notify_fn({
+            let notified = notified.clone();
+            move |diff| notified.lock().unwrap().push(diff.clone())
+        });
+
+        buffer.update(0, 0); // moves to the even group
+        buffer.update(3, 7); // moves to the odd group
+        even.0.update();
+        assert_eq!(even_view.iter().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(
+            *notified.lock().unwrap(),
+            vec![SeqDiff::Shift { at: 0, by: 1 }, SeqDiff::Shift { at: 2, by: -1 }]
+        );
+
+        // the odd group disappears
+        for idx in [0, 2, 3, 4] {
+            buffer.update(idx, 10);
+        }
+        groups.0.update();
+        assert!(groups_view.get(&false).is_none());
+        assert_eq!(even_view.len(), Some(5));
+
+        // an empty group is kept and gets updates once its key reappears
+        for idx in 0..5 {
+            buffer.update(idx, 1);
+        }
+        groups.0.update();
+        assert!(groups_view.get(&true).is_none());
+        assert_eq!(even_view.len(), Some(0));
+
+        buffer.update(2, 4);
+        groups.0.update();
+        assert!(groups_view.get(&true).is_some());
+        assert_eq!(even_view.iter().collect::<Vec<_>>(), vec![4]);
+    }
+
+    #[test]
+    fn group_seq_shift() {
+        let mut buffer = VecBuffer::with_data((0..20).collect::<Vec<usize>>());
+        let groups = buffer.get_port().to_sequence().group_by(|x| x % 3);
+        let groups_view = groups.get_view().unwrap();
+
+        buffer.insert(0, 30);
+        buffer.insert(7, 31);
+        buffer.remove(12);
+        buffer.remove(3);
+        buffer.update(5, 32);
+        buffer.push(33);
+        groups.0.update();
+
+        let data = buffer.get_port().get_view().unwrap().read().unwrap().clone();
+        for key in 0..3 {
+            let group = groups_view.get(&key).unwrap().get_view().unwrap();
+            assert_eq!(
+                group.iter().collect::<Vec<_>>(),
+                data.iter().cloned().filter(|x| x % 3 == key).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use {
+    crate::{
+        view::{
+            InnerViewPort, Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            index::{IndexArea, IndexView},
+        },
+        projection::projection_helper::ProjectionHelper,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::{collections::HashSet, hash::Hash},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Key, A> OuterViewPort<dyn IndexView<Key, Item = A>>
+where
+    Key: Clone + PartialOrd + Hash + Eq + Send + Sync + 'static,
+    A: 'static,
+{
+    /// pairs of items with keys present in both views
+    pub fn join<B: 'static>(
+        &self,
+        other: &OuterViewPort<dyn IndexView<Key, Item = B>>,
+    ) -> OuterViewPort<dyn IndexView<Key, Item = (A, B)>> {
+        self.join_with(other, JoinKind::Inner, |a, b| Some((a?, b?)))
+    }
+
+    /// all items of `self`, paired with the item of `other` if present
+    pub fn left_join<B: 'static>(
+        &self,
+        other: &OuterViewPort<dyn IndexView<Key, Item = B>>,
+    ) -> OuterViewPort<dyn IndexView<Key, Item = (A, Option<B>)>> {
+        self.join_with(other, JoinKind::Left, |a, b| Some((a?, b)))
+    }
+
+    /// all keys present in either view
+    pub fn outer_join<B: 'static>(
+        &self,
+        other: &OuterViewPort<dyn IndexView<Key, Item = B>>,
+    ) -> OuterViewPort<dyn IndexView<Key, Item = (Option<A>, Option<B>)>> {
+        self.join_with(other, JoinKind::Outer, |a, b| {
+            if a.is_some() || b.is_some() {
+                Some((a, b))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn join_with<B: 'static, Item: 'static>(
+        &self,
+        other: &OuterViewPort<dyn IndexView<Key, Item = B>>,
+        kind: JoinKind,
+        combine: fn(Option<A>, Option<B>) -> Option<Item>,
+    ) -> OuterViewPort<dyn IndexView<Key, Item = Item>> {
+        let port = ViewPort::new();
+        JoinIndex::new(self.clone(), other.clone(), kind, combine, port.inner());
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JoinKind {
+    Inner,
+    Left,
+    Outer,
+}
+
+type IndexArg<Key, Item> = Arc<RwLock<Option<Arc<dyn IndexView<Key, Item = Item>>>>>;
+
+/// Keeps the set of joined keys, so a change on either side is only forwarded
+/// for keys which are joined before or after it. This also covers a key removed
+/// from both sides in the same update, where neither side has it anymore.
+pub struct JoinIndex<Key, A, B, Item>
+where
+    Key: Clone + PartialOrd + Hash + Eq + Send + Sync + 'static,
+    A: 'static,
+    B: 'static,
+    Item: 'static,
+{
+    a: IndexArg<Key, A>,
+    b: IndexArg<Key, B>,
+    kind: JoinKind,
+    combine: fn(Option<A>, Option<B>) -> Option<Item>,
+    joined: HashSet<Key>,
+    cast: Arc<RwLock<ObserverBroadcast<dyn IndexView<Key, Item = Item>>>>,
+    proj_helper: ProjectionHelper<usize, Self>,
+}
+
+impl<Key, A, B, Item> JoinIndex<Key, A, B, Item>
+where
+    Key: Clone + PartialOrd + Hash + Eq + Send + Sync + 'static,
+    A: 'static,
+    B: 'static,
+    Item: 'static,
+{
+    pub fn new(
+        a_port: OuterViewPort<dyn IndexView<Key, Item = A>>,
+        b_port: OuterViewPort<dyn IndexView<Key, Item = B>>,
+        kind: JoinKind,
+        combine: fn(Option<A>, Option<B>) -> Option<Item>,
+        out_port: InnerViewPort<dyn IndexView<Key, Item = Item>>,
+    ) -> Arc<RwLock<Self>> {
+        let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
+
+        let join = Arc::new(RwLock::new(JoinIndex {
+            a: proj_helper.new_index_arg(0, a_port, |s: &mut Self, area| {
+                s.update_keys(area);
+            }),
+            b: proj_helper.new_index_arg(1, b_port, |s: &mut Self, area| {
+                s.update_keys(area);
+            }),
+            kind,
+            combine,
+            joined: HashSet::new(),
+            cast: out_port.get_broadcast(),
+            proj_helper,
+        }));
+
+        join.write().unwrap().proj_helper.set_proj(&join);
+        out_port.set_view(Some(join.clone()));
+        join
+    }
+
+    /// forward the keys of `area` which are joined before or after the change
+    fn update_keys(&mut self, area: &IndexArea<Key>) {
+        let Some(keys) = area.keys() else {
+            // all joined keys may have changed
+            self.joined = self.area().keys()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|key| self.get(key).is_some())
+                .collect();
+            self.cast.notify(area);
+            return;
+        };
+
+        let mut changed = Vec::new();
+        for key in keys {
+            let was = self.joined.contains(&key);
+            let now = self.get(&key).is_some();
+            if now {
+                self.joined.insert(key.clone());
+            } else {
+                self.joined.remove(&key);
+            }
+            if was || now {
+                changed.push(key);
+            }
+        }
+
+        if !changed.is_empty() {
+            self.cast.notify(&IndexArea::Set(changed));
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Key, A, B, Item> View for JoinIndex<Key, A, B, Item>
+where
+    Key: Clone + PartialOrd + Hash + Eq + Send + Sync + 'static,
+    A: 'static,
+    B: 'static,
+    Item: 'static,
+{
+    type Msg = IndexArea<Key>;
+}
+
+impl<Key, A, B, Item> IndexView<Key> for JoinIndex<Key, A, B, Item>
+where
+    Key: Clone + PartialOrd + Hash + Eq + Send + Sync + 'static,
+    A: 'static,
+    B: 'static,
+    Item: 'static,
+{
+    type Item = Item;
+
+    fn get(&self, key: &Key) -> Option<Item> {
+        (self.combine)(self.a.get(key), self.b.get(key))
+    }
+
+    fn area(&self) -> IndexArea<Key> {
+        match self.kind {
+            JoinKind::Inner => self.a.area().intersection(&self.b.area()),
+            JoinKind::Left => self.a.area(),
+            JoinKind::Outer => self.a.area().merge(&self.b.area()),
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::index_hashmap::*;
+    use crate::view::{port::UpdateTask, index::{IndexArea, IndexView}};
+
+    #[test]
+    fn join_index1() {
+        let mut symbols = IndexBuffer::<&str, u32>::new();
+        let mut diagnostics = IndexBuffer::<&str, String>::new();
+        symbols.insert("main", 1);
+        symbols.insert("foo", 10);
+        diagnostics.insert("foo", "unused".into());
+
+        let inner = symbols.get_port().join(&diagnostics.get_port());
+        let left = symbols.get_port().left_join(&diagnostics.get_port());
+        let outer = symbols.get_port().outer_join(&diagnostics.get_port());
+
+        let inner_view = inner.get_view().unwrap();
+        assert_eq!(inner_view.get(&"foo"), Some((10, "unused".to_string())));
+        assert_eq!(inner_view.get(&"main"), None);
+        assert_eq!(left.get_view().unwrap().get(&"main"), Some((1, None)));
+
+        let notified = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _obs = inner.add_notify_fn({
+            let notified = notified.clone();
+            move |area| if let IndexArea::Set(keys) = area {
+                notified.lock().unwrap().extend(keys.iter().cloned())
+            }
+        });
+
+        diagnostics.insert("bar", "undefined".into()); // no symbol
+        diagnostics.insert("main", "shadowed".into());
+        inner.0.update();
+        outer.0.update();
+
+        assert_eq!(*notified.lock().unwrap(), vec!["main"]);
+        assert_eq!(inner_view.get(&"main"), Some((1, "shadowed".to_string())));
+        assert_eq!(outer.get_view().unwrap().get(&"bar"), Some((None, Some("undefined".to_string()))));
+
+        // removed from both sides within one update
+        notified.lock().unwrap().clear();
+        symbols.remove("foo");
+        diagnostics.remove("foo");
+        inner.0.update();
+        assert_eq!(*notified.lock().unwrap(), vec!["foo"]);
+        assert_eq!(inner_view.get(&"foo"), None);
+    }
+
+    #[test]
+    fn join_one_sided() {
+        let mut symbols = IndexBuffer::<&str, u32>::new();
+        let diagnostics = IndexBuffer::<&str, String>::new();
+        let inner = symbols.get_port().join(&diagnostics.get_port());
+        let left = symbols.get_port().left_join(&diagnostics.get_port());
+
+        let inner_notified = std::sync::Arc::new(std::sync::Mutex::new(0));
+        let _inner_obs = inner.add_notify_fn({
+            let notified = inner_notified.clone();
+            move |_| *notified.lock().unwrap() += 1
+        });
+        let left_notified = std::sync::Arc::new(std::sync::Mutex::new(0));
+        let _left_obs = left.add_notify_fn({
+            let notified = left_notified.clone();
+            move |_| *notified.lock().unwrap() += 1
+        });
+
+        symbols.insert("main", 1);
+        inner.0.update();
+        left.0.update();
+        assert_eq!(*inner_notified.lock().unwrap(), 0);
+        assert_eq!(*left_notified.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn join_area() {
+        let a = IndexArea::Set(vec![1, 2, 3]);
+        let b = IndexArea::Range(2..=5);
+
+        assert!(matches!(a.intersection(&b), IndexArea::Set(keys) if keys == vec![2, 3]));
+        assert!(matches!(a.merge(&b), IndexArea::Range(r) if r == (1..=5)));
+        assert!(matches!(b.intersection(&IndexArea::Range(6..=7)), IndexArea::Empty));
+        assert!(matches!(IndexArea::Empty.merge(&a), IndexArea::Set(_)));
+    }
+}
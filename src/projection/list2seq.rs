@@ -2,7 +2,7 @@ use {
     crate::{
         view::{
             InnerViewPort, Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
-            sequence::SequenceView,
+            sequence::{SeqDiff, SequenceView},
             list::{ListView, ListDiff}
         },
         buffer::vec::VecDiff,
@@ -52,25 +52,25 @@ where
         };
 
         self.cur_len = new_len;
-        self.cast.notify_each(0..std::cmp::max(old_len, new_len));
+        self.cast.notify_each(SeqDiff::replace(old_len, new_len));
     }
 
     fn notify(&mut self, diff: &ListDiff<T>) {
         match diff {
             ListDiff::Clear => {
-                self.cast.notify_each(0..self.cur_len);
+                self.cast.notify_each(SeqDiff::replace(self.cur_len, 0));
                 self.cur_len = 0
             }
             ListDiff::Remove(idx) => {
-                self.cast.notify_each(*idx..self.cur_len);
+                self.cast.notify(&SeqDiff::Shift { at: *idx, by: -1 });
                 self.cur_len -= 1;
             }
             ListDiff::Insert { idx, val: _ } => {
                 self.cur_len += 1;
-                self.cast.notify_each(*idx..self.cur_len);
+                self.cast.notify(&SeqDiff::Shift { at: *idx, by: 1 });
             }
             ListDiff::Update { idx, val: _ } => {
-                self.cast.notify(&idx);
+                self.cast.notify(&SeqDiff::Range(*idx..*idx + 1));
             }
         }
     }
@@ -80,7 +80,7 @@ impl<T> View for List2Seq<T>
 where
     T: Clone + Send + Sync + 'static,
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<T> SequenceView for List2Seq<T>
@@ -158,6 +158,22 @@ mod tests {
         assert_eq!(seq_view.get_view().get(&1), Some('c'));
         assert_eq!(seq_view.get_view().get(&2), None);
     }
-}
-
 
+    #[test]
+    fn insert_front_single_diff() {
+        let mut buf = VecBuffer::<char>::with_data("hello world".chars().collect());
+        let seq_port = buf.get_port().to_list().to_sequence();
+
+        let notified = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _obs = seq_port.add_notify_fn({
+            let notified = notified.clone();
+            move |diff| notified.lock().unwrap().push(diff.clone())
+        });
+        notified.lock().unwrap().clear();
+
+        buf.insert(0, '>');
+        seq_port.0.update();
+        assert_eq!(*notified.lock().unwrap(), vec![SeqDiff::Shift { at: 0, by: 1 }]);
+        assert_eq!(seq_port.get_view().get(&0), Some('>'));
+    }
+}
@@ -2,7 +2,7 @@ use {
     crate::{
         view::{
             Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
-            sequence::SequenceView,
+            sequence::{SeqDiff, SequenceView},
         },
     },
     std::sync::Arc,
@@ -50,7 +50,7 @@ where
     SrcView: SequenceView + ?Sized,
     F: Fn(&SrcView::Item) -> DstItem + Send + Sync,
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<DstItem, SrcView, F> SequenceView for MapSequenceItem<DstItem, SrcView, F>
@@ -77,19 +77,14 @@ where
     F: Fn(&SrcView::Item) -> DstItem + Send + Sync,
 {
     fn reset(&mut self, view: Option<Arc<SrcView>>) {
-        let old_len = self.len();
+        let old_len = self.len().unwrap_or(0);
         self.src_view = view;
-        let new_len = self.len();
-
-        if let Some(len) = old_len {
-            self.cast.notify_each(0..len);
-        }
-        if let Some(len) = new_len {
-            self.cast.notify_each(0..len);
-        }
+        let new_len = self.len().unwrap_or(0);
+
+        self.cast.notify_each(SeqDiff::replace(old_len, new_len));
     }
 
-    fn notify(&mut self, msg: &usize) {
+    fn notify(&mut self, msg: &SeqDiff) {
         self.cast.notify(msg);
     }
 }
@@ -6,18 +6,42 @@ pub mod sgl2seq;
 pub mod vec2seq;
 pub mod vec2bin;
 pub mod vec2json;
+pub mod serialize_diff;
+pub mod view2json;
+pub mod view2bin;
+pub mod diff_log;
 pub mod vec2list;
 pub mod list2seq;
 pub mod seq2idx;
 pub mod enumerate_sequence;
+pub mod enumerate_list;
+pub(crate) mod order_tree;
+pub(crate) mod flag_tree;
 pub mod filter_sequence;
+pub mod filter_list;
+pub(crate) mod permutation;
+pub mod sort_sequence;
+pub mod sort_list;
+pub mod reduce;
+pub mod group_sequence;
+pub mod group_list;
+pub mod join_index;
 pub mod filter_map_sequence;
 pub mod flatten_singleton;
 pub mod flatten_sequence;
+pub mod flatten_list;
 pub mod flatten_grid;
 pub mod map_singleton;
+pub mod combine_singleton;
+pub mod distinct_singleton;
 pub mod map_sequence;
+pub mod slice_sequence;
+pub mod rev_sequence;
 pub mod map_list;
+pub mod slice_list;
+pub mod rev_list;
+pub mod concat_list;
+pub mod zip_list;
 pub mod map_index_item;
 pub mod map_index_key;
 pub mod grid_offset;
@@ -0,0 +1,413 @@
+use std::{cmp::min, ops::Range};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+const NIL: usize = usize::MAX;
+
+struct Node {
+    prio: u64,
+    left: usize,
+    right: usize,
+    parent: usize,
+
+    /// number of nodes and sum of weights in this subtree
+    size: usize,
+    weight: usize,
+}
+
+/// Sequence of values stored as an implicit treap,
+/// so inserting, removing and looking up by position are logarithmic.
+///
+/// Every value is referred to by a handle which stays valid until it is removed,
+/// its current position is found by walking up to the root.
+/// Every value also has a weight, for lookups by the sum of weights.
+pub(crate) struct OrderTree<T> {
+    nodes: Vec<Node>,
+    vals: Vec<Option<T>>,
+    free: Vec<usize>,
+    root: usize,
+
+    seed: u64,
+    weight_fn: fn(&T) -> usize,
+}
+
+impl<T> OrderTree<T> {
+    pub(crate) fn new(weight_fn: fn(&T) -> usize) -> Self {
+        OrderTree {
+            nodes: Vec::new(),
+            vals: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            seed: 0x2545f4914f6cdd1d,
+            weight_fn,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    /// sum of all weights
+    pub(crate) fn total(&self) -> usize {
+        self.weight(self.root)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+        self.vals.clear();
+        self.free.clear();
+        self.root = NIL;
+    }
+
+    pub(crate) fn get(&self, handle: usize) -> &T {
+        self.vals[handle].as_ref().unwrap()
+    }
+
+    /// modify the value of `handle`, which may change its weight
+    pub(crate) fn update<R>(&mut self, handle: usize, f: impl FnOnce(&mut T) -> R) -> R {
+        let r = f(self.vals[handle].as_mut().unwrap());
+        let mut n = handle;
+        while n != NIL {
+            self.pull(n);
+            n = self.nodes[n].parent;
+        }
+        r
+    }
+
+    /// handle of the value at `pos`
+    pub(crate) fn at(&self, mut pos: usize) -> Option<usize> {
+        let mut n = self.root;
+        while n != NIL {
+            let left = self.nodes[n].left;
+            let ls = self.size(left);
+            if pos < ls {
+                n = left;
+            } else if pos == ls {
+                return Some(n);
+            } else {
+                pos -= ls + 1;
+                n = self.nodes[n].right;
+            }
+        }
+        None
+    }
+
+    /// current position of `handle`
+    pub(crate) fn position(&self, handle: usize) -> usize {
+        let mut pos = self.size(self.nodes[handle].left);
+        let mut n = handle;
+        loop {
+            let parent = self.nodes[n].parent;
+            if parent == NIL {
+                return pos;
+            }
+            if self.nodes[parent].right == n {
+                pos += self.size(self.nodes[parent].left) + 1;
+            }
+            n = parent;
+        }
+    }
+
+    /// sum of the weights before `pos`
+    pub(crate) fn weight_before(&self, mut pos: usize) -> usize {
+        let mut sum = 0;
+        let mut n = self.root;
+        while n != NIL && pos > 0 {
+            let left = self.nodes[n].left;
+            let ls = self.size(left);
+            if pos <= ls {
+                n = left;
+            } else {
+                sum += self.weight(left) + self.own_weight(n);
+                pos -= ls + 1;
+                n = self.nodes[n].right;
+            }
+        }
+        sum
+    }
+
+    /// position of the value at which the sum of weights exceeds `k`
+    pub(crate) fn select_weight(&self, mut k: usize) -> Option<usize> {
+        let mut pos = 0;
+        let mut n = self.root;
+        while n != NIL {
+            let left = self.nodes[n].left;
+            let (lw, w) = (self.weight(left), self.own_weight(n));
+            if k < lw {
+                n = left;
+            } else if k < lw + w {
+                return Some(pos + self.size(left));
+            } else {
+                k -= lw + w;
+                pos += self.size(left) + 1;
+                n = self.nodes[n].right;
+            }
+        }
+        None
+    }
+
+    /// number of leading values for which `pred` holds,
+    /// `pred` gets the handle and must be monotonic
+    pub(crate) fn partition_point(&self, mut pred: impl FnMut(usize) -> bool) -> usize {
+        let mut pos = 0;
+        let mut n = self.root;
+        while n != NIL {
+            if pred(n) {
+                pos += self.size(self.nodes[n].left) + 1;
+                n = self.nodes[n].right;
+            } else {
+                n = self.nodes[n].left;
+            }
+        }
+        pos
+    }
+
+    /// insert `val` at `pos`, returns its handle
+    pub(crate) fn insert(&mut self, pos: usize, val: T) -> usize {
+        let handle = self.alloc(val);
+        self.pull(handle);
+        self.splice(pos, handle);
+        handle
+    }
+
+    /// insert all `vals` starting at `pos`, returns their handles
+    pub(crate) fn insert_iter(&mut self, pos: usize, vals: impl IntoIterator<Item = T>) -> Vec<usize> {
+        // build the treap in linear time from its right spine
+        let mut handles = Vec::new();
+        let mut spine: Vec<usize> = Vec::new();
+        for val in vals {
+            let handle = self.alloc(val);
+            handles.push(handle);
+
+            let mut last = NIL;
+            while let Some(&top) = spine.last() {
+                if self.nodes[top].prio >= self.nodes[handle].prio {
+                    break;
+                }
+                self.pull(top);
+                last = spine.pop().unwrap();
+            }
+            self.nodes[handle].left = last;
+            if let Some(&top) = spine.last() {
+                self.nodes[top].right = handle;
+            }
+            spine.push(handle);
+        }
+
+        while let Some(top) = spine.pop() {
+            self.pull(top);
+            if spine.is_empty() {
+                self.splice(pos, top);
+            }
+        }
+        handles
+    }
+
+    /// remove the value of `handle`
+    pub(crate) fn remove(&mut self, handle: usize) -> T {
+        let pos = self.position(handle);
+        let (a, b) = self.split(self.root, pos);
+        let (_, c) = self.split(b, 1);
+        let root = self.merge(a, c);
+        self.set_root(root);
+
+        self.free.push(handle);
+        self.vals[handle].take().unwrap()
+    }
+
+    /// remove the values in `range`, returns the sum of their weights
+    pub(crate) fn remove_range(&mut self, range: Range<usize>) -> usize {
+        let end = min(range.end, self.len());
+        let start = min(range.start, end);
+
+        let (a, b) = self.split(self.root, start);
+        let (m, c) = self.split(b, end - start);
+        let root = self.merge(a, c);
+        self.set_root(root);
+
+        let removed = self.weight(m);
+        let mut stack = vec![m];
+        while let Some(n) = stack.pop() {
+            if n != NIL {
+                stack.push(self.nodes[n].left);
+                stack.push(self.nodes[n].right);
+                self.vals[n] = None;
+                self.free.push(n);
+            }
+        }
+        removed
+    }
+
+    //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+    fn alloc(&mut self, val: T) -> usize {
+        // xorshift64
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+
+        let node = Node {
+            prio: self.seed,
+            left: NIL,
+            right: NIL,
+            parent: NIL,
+            size: 1,
+            weight: 0,
+        };
+
+        if let Some(handle) = self.free.pop() {
+            self.nodes[handle] = node;
+            self.vals[handle] = Some(val);
+            handle
+        } else {
+            self.nodes.push(node);
+            self.vals.push(Some(val));
+            self.nodes.len() - 1
+        }
+    }
+
+    fn size(&self, n: usize) -> usize {
+        if n == NIL { 0 } else { self.nodes[n].size }
+    }
+
+    fn weight(&self, n: usize) -> usize {
+        if n == NIL { 0 } else { self.nodes[n].weight }
+    }
+
+    fn own_weight(&self, n: usize) -> usize {
+        (self.weight_fn)(self.get(n))
+    }
+
+    /// recalculate the aggregates of `n` from its children
+    fn pull(&mut self, n: usize) {
+        let (left, right) = (self.nodes[n].left, self.nodes[n].right);
+        self.nodes[n].size = self.size(left) + 1 + self.size(right);
+        self.nodes[n].weight = self.weight(left) + self.own_weight(n) + self.weight(right);
+        for child in [left, right] {
+            if child != NIL {
+                self.nodes[child].parent = n;
+            }
+        }
+    }
+
+    fn set_root(&mut self, root: usize) {
+        self.root = root;
+        if root != NIL {
+            self.nodes[root].parent = NIL;
+        }
+    }
+
+    /// insert the subtree `t` at `pos`
+    fn splice(&mut self, pos: usize, t: usize) {
+        let (a, b) = self.split(self.root, pos);
+        let ab = self.merge(a, t);
+        let root = self.merge(ab, b);
+        self.set_root(root);
+    }
+
+    /// split `t` into its first `k` values and the rest
+    fn split(&mut self, t: usize, k: usize) -> (usize, usize) {
+        if t == NIL {
+            return (NIL, NIL);
+        }
+
+        let ls = self.size(self.nodes[t].left);
+        if k <= ls {
+            let (a, b) = self.split(self.nodes[t].left, k);
+            self.nodes[t].left = b;
+            self.pull(t);
+            (a, t)
+        } else {
+            let (a, b) = self.split(self.nodes[t].right, k - ls - 1);
+            self.nodes[t].right = a;
+            self.pull(t);
+            (t, b)
+        }
+    }
+
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+
+        if self.nodes[a].prio > self.nodes[b].prio {
+            let right = self.merge(self.nodes[a].right, b);
+            self.nodes[a].right = right;
+            self.pull(a);
+            a
+        } else {
+            let left = self.merge(a, self.nodes[b].left);
+            self.nodes[b].left = left;
+            self.pull(b);
+            b
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{projection::order_tree::*, testing::Rng};
+
+    #[test]
+    fn order_tree_random() {
+        let mut rng = Rng::new(7);
+        let mut tree = OrderTree::new(|x: &u32| *x as usize % 2);
+        let mut model: Vec<(u32, usize)> = Vec::new();
+
+        for step in 0..2000 {
+            match rng.below(5) {
+                0 | 1 => {
+                    let pos = rng.below(model.len() + 1);
+                    let val = rng.below(100) as u32;
+                    model.insert(pos, (val, tree.insert(pos, val)));
+                }
+                2 => {
+                    let pos = rng.below(model.len() + 1);
+                    let vals: Vec<u32> = (0..rng.below(6)).map(|_| rng.below(100) as u32).collect();
+                    let handles = tree.insert_iter(pos, vals.clone());
+                    model.splice(pos..pos, vals.into_iter().zip(handles));
+                }
+                3 if !model.is_empty() => {
+                    let pos = rng.below(model.len());
+                    let (val, handle) = model.remove(pos);
+                    assert_eq!(tree.remove(handle), val);
+                }
+                3 => {}
+                _ => {
+                    let start = rng.below(model.len() + 1);
+                    let end = start + rng.below(4);
+                    let removed: Vec<_> = model.drain(start..end.min(model.len())).collect();
+                    let weight = removed.iter().map(|(x, _)| *x as usize % 2).sum::<usize>();
+                    assert_eq!(tree.remove_range(start..end), weight);
+                }
+            }
+
+            if step % 50 == 0 && !model.is_empty() {
+                let pos = rng.below(model.len());
+                tree.update(model[pos].1, |x| *x += 1);
+                model[pos].0 += 1;
+            }
+
+            assert_eq!(tree.len(), model.len());
+            for (pos, (val, handle)) in model.iter().enumerate() {
+                assert_eq!(tree.at(pos), Some(*handle));
+                assert_eq!(tree.position(*handle), pos);
+                assert_eq!(tree.get(*handle), val);
+            }
+
+            let odd: Vec<usize> = (0..model.len()).filter(|pos| model[*pos].0 % 2 == 1).collect();
+            assert_eq!(tree.total(), odd.len());
+            for (k, pos) in odd.iter().enumerate() {
+                assert_eq!(tree.select_weight(k), Some(*pos));
+                assert_eq!(tree.weight_before(*pos), k);
+            }
+            assert_eq!(tree.select_weight(odd.len()), None);
+            assert_eq!(tree.partition_point(|h| tree.position(h) < model.len() / 2), model.len() / 2);
+        }
+    }
+}
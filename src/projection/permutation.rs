@@ -0,0 +1,149 @@
+use {
+    crate::projection::order_tree::OrderTree,
+    std::cmp::Ordering,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Sorting permutation of a list which is updated one item at a time.
+/// Equal items keep the order of their source indices.
+///
+/// Source order and sorted order are two trees which link to each other,
+/// so an update takes `O(log² n)` since comparing equal items needs their source indices.
+pub(crate) struct Permutation<Item, F>
+where
+    F: Fn(&Item, &Item) -> Ordering,
+{
+    /// items in source order, along with their node in `sorted`
+    items: OrderTree<(Item, usize)>,
+
+    /// node in `items` of every sorted position
+    sorted: OrderTree<usize>,
+
+    cmp: F,
+}
+
+impl<Item, F> Permutation<Item, F>
+where
+    F: Fn(&Item, &Item) -> Ordering,
+{
+    pub(crate) fn new(cmp: F) -> Self {
+        Permutation {
+            items: OrderTree::new(|_| 0),
+            sorted: OrderTree::new(|_| 0),
+            cmp,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// item at the sorted position `pos`
+    pub(crate) fn get(&self, pos: usize) -> Option<&Item> {
+        Some(&self.items.get(*self.sorted.get(self.sorted.at(pos)?)).0)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.items.clear();
+        self.sorted.clear();
+    }
+
+    /// replace all items, sorting them at once
+    pub(crate) fn reset(&mut self, items: impl IntoIterator<Item = Item>) {
+        self.clear();
+
+        let handles = self.items.insert_iter(0, items.into_iter().map(|val| (val, 0)));
+        let mut order: Vec<usize> = handles.clone();
+        order.sort_by(|a, b| (self.cmp)(&self.items.get(*a).0, &self.items.get(*b).0));
+
+        let sorted_handles = self.sorted.insert_iter(0, order.iter().cloned());
+        for (handle, sorted_handle) in order.into_iter().zip(sorted_handles) {
+            self.items.update(handle, |(_, s)| *s = sorted_handle);
+        }
+    }
+
+    /// sorted position for the item at `handle` in `items`,
+    /// which must not be contained in `sorted`
+    fn position(&self, handle: usize) -> usize {
+        let (val, idx) = (&self.items.get(handle).0, self.items.position(handle));
+        self.sorted.partition_point(|s| {
+            let j = *self.sorted.get(s);
+            (self.cmp)(&self.items.get(j).0, val)
+                .then(self.items.position(j).cmp(&idx))
+                == Ordering::Less
+        })
+    }
+
+    /// add the item at `handle` in `items` to the sorted order
+    fn link(&mut self, handle: usize) -> usize {
+        let pos = self.position(handle);
+        let sorted_handle = self.sorted.insert(pos, handle);
+        self.items.update(handle, |(_, s)| *s = sorted_handle);
+        pos
+    }
+
+    /// remove the item at `handle` in `items` from the sorted order
+    fn unlink(&mut self, handle: usize) -> usize {
+        let sorted_handle = self.items.get(handle).1;
+        let pos = self.sorted.position(sorted_handle);
+        self.sorted.remove(sorted_handle);
+        pos
+    }
+
+    /// insert `val` at the source index `idx`, returns its sorted position
+    pub(crate) fn insert(&mut self, idx: usize, val: Item) -> usize {
+        let handle = self.items.insert(idx, (val, 0));
+        self.link(handle)
+    }
+
+    /// remove the item at the source index `idx`, returns its sorted position
+    pub(crate) fn remove(&mut self, idx: usize) -> usize {
+        let handle = self.items.at(idx).unwrap();
+        let pos = self.unlink(handle);
+        self.items.remove(handle);
+        pos
+    }
+
+    /// replace the item at the source index `idx`,
+    /// returns its old and new sorted position
+    pub(crate) fn update(&mut self, idx: usize, val: Item) -> (usize, usize) {
+        let handle = self.items.at(idx).unwrap();
+        let old_pos = self.unlink(handle);
+        self.items.update(handle, |(v, _)| *v = val);
+        (old_pos, self.link(handle))
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::projection::permutation::*;
+
+    #[test]
+    fn permutation_stable() {
+        let mut perm = Permutation::new(|a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0));
+
+        assert_eq!(perm.insert(0, (2, 'a')), 0);
+        assert_eq!(perm.insert(1, (1, 'b')), 0);
+        assert_eq!(perm.insert(0, (2, 'c')), 1);
+        assert_eq!(perm.insert(3, (2, 'd')), 3);
+
+        fn sorted(perm: &Permutation<(i32, char), impl Fn(&(i32, char), &(i32, char)) -> Ordering>) -> String {
+            (0..perm.len()).map(|i| perm.get(i).unwrap().1).collect()
+        }
+        assert_eq!(sorted(&perm), "bcad");
+
+        assert_eq!(perm.update(0, (0, 'c')), (1, 0));
+        assert_eq!(sorted(&perm), "cbad");
+
+        assert_eq!(perm.remove(1), 2);
+        assert_eq!(sorted(&perm), "cbd");
+
+        perm.reset(vec![(3, 'x'), (1, 'y'), (3, 'z'), (0, 'w')]);
+        assert_eq!(sorted(&perm), "wyxz");
+        assert_eq!(perm.insert(1, (3, 'v')), 3);
+        assert_eq!(sorted(&perm), "wyxvz");
+    }
+}
@@ -1,18 +1,18 @@
 use {
     crate::{
         view::{
-            channel::{queue_channel, set_channel, ChannelData, ChannelReceiver, ChannelSender},
+            channel::{channel, queue_channel, set_channel, ChannelData, ChannelReceiver, ChannelSender},
             port::UpdateTask,
-            Observer, ObserverExt, OuterViewPort, View,
+            Observer, OuterViewPort, View, ViewSnapshot,
             index::{IndexArea, IndexView},
-            sequence::SequenceView,
+            list::{ListDiff, ListView},
+            sequence::{SeqDiff, SeqDiffBatch, SequenceView},
             singleton::SingletonView,
         },
     },
     std::sync::RwLock,
     std::{
         any::Any,
-        cmp::max,
         collections::HashMap,
         hash::Hash,
         sync::{Arc, Weak},
@@ -65,9 +65,19 @@ where
         &mut self,
         arg_key: ArgKey,
         port: OuterViewPort<dyn SequenceView<Item = Item>>,
-        notify: impl Fn(&mut P, &usize) + Send + Sync + 'static,
+        notify: impl Fn(&mut P, &SeqDiff) + Send + Sync + 'static,
     ) -> Arc<RwLock<Option<Arc<dyn SequenceView<Item = Item>>>>> {
-        port.add_observer(self.new_arg(arg_key, Arc::new(port.0.clone()), notify, set_channel()));
+        port.add_observer(self.new_arg(arg_key, Arc::new(port.0.clone()), notify, channel::<SeqDiffBatch>()));
+        port.get_view_arc()
+    }
+
+    pub fn new_list_arg<Item: Clone + Send + Sync + 'static>(
+        &mut self,
+        arg_key: ArgKey,
+        port: OuterViewPort<dyn ListView<Item>>,
+        notify: impl Fn(&mut P, &ListDiff<Item>) + Send + Sync + 'static,
+    ) -> Arc<RwLock<Option<Arc<dyn ListView<Item>>>>> {
+        port.add_observer(self.new_arg(arg_key, Arc::new(port.0.clone()), notify, queue_channel()));
         port.get_view_arc()
     }
 
@@ -141,6 +151,24 @@ where
     tx: ChannelSender<D>,
 }
 
+impl<P, V, D> ProjectionArg<P, V, D>
+where
+    P: Send + Sync + 'static,
+    V: View + ?Sized,
+    D: ChannelData<Item = V::Msg>,
+    D::IntoIter: Send + Sync,
+{
+    /// process all pending messages with an already locked projection,
+    /// e.g. when an argument is added from within a notify handler
+    pub fn flush(&self, proj: &mut P) {
+        if let Some(data) = self.rx.try_recv() {
+            for msg in data {
+                (self.notify)(proj, &msg);
+            }
+        }
+    }
+}
+
 impl<P, V, D> UpdateTask for ProjectionArg<P, V, D>
 where
     P: Send + Sync + 'static,
@@ -199,19 +227,18 @@ impl<P, Item, D> Observer<dyn SequenceView<Item = Item>>
     for ProjectionArg<P, dyn SequenceView<Item = Item>, D>
 where
     P: Send + Sync + 'static,
-    D: ChannelData<Item = usize>,
+    D: ChannelData<Item = SeqDiff>,
     D::IntoIter: Send + Sync,
 {
     fn reset(&mut self, new_src: Option<Arc<dyn SequenceView<Item = Item>>>) {
-        let old_len = self.src.len().unwrap_or(0);
-        self.src = new_src;
-        let new_len = self.src.len().unwrap_or(0);
-
-        self.notify_each(0..max(old_len, new_len));
+        let old_src = std::mem::replace(&mut self.src, new_src);
+        for diff in <dyn SequenceView<Item = Item>>::snapshot(old_src, self.src.clone()) {
+            self.notify(&diff);
+        }
     }
 
-    fn notify(&mut self, msg: &usize) {
-        self.tx.send(*msg);
+    fn notify(&mut self, msg: &SeqDiff) {
+        self.tx.send(msg.clone());
     }
 }
 
@@ -237,3 +264,25 @@ where
         self.tx.send(msg.clone());
     }
 }
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<P, Item, D> Observer<dyn ListView<Item>>
+    for ProjectionArg<P, dyn ListView<Item>, D>
+where
+    P: Send + Sync + 'static,
+    Item: Clone + Send + Sync + 'static,
+    D: ChannelData<Item = ListDiff<Item>>,
+    D::IntoIter: Send + Sync,
+{
+    fn reset(&mut self, new_src: Option<Arc<dyn ListView<Item>>>) {
+        let old_src = std::mem::replace(&mut self.src, new_src);
+        for diff in <dyn ListView<Item>>::snapshot(old_src, self.src.clone()) {
+            self.notify(&diff);
+        }
+    }
+
+    fn notify(&mut self, msg: &ListDiff<Item>) {
+        self.tx.send(msg.clone());
+    }
+}
@@ -0,0 +1,566 @@
+use {
+    crate::{
+        view::{
+            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            index::{IndexArea, IndexView},
+            list::{ListDiff, ListView, ListViewExt},
+            sequence::{SeqDiff, SeqDiffBatch, SequenceView, SequenceViewExt},
+            singleton::SingletonView,
+        },
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::{
+        collections::HashMap,
+        hash::Hash,
+        ops::{Add, Sub},
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+type AddFn<Item, A> = Box<dyn Fn(&A, &Item) -> A + Send + Sync>;
+type RemoveFn<Item, A> = Box<dyn Fn(&A, &Item) -> Option<A> + Send + Sync>;
+
+/// Aggregate which is updated one item at a time.
+pub struct Fold<Item, A> {
+    init: A,
+    acc: A,
+    add: AddFn<Item, A>,
+
+    /// returns `None` if the aggregate has to be
+    /// recomputed from the remaining items
+    remove: RemoveFn<Item, A>,
+}
+
+impl<Item, A: Clone> Fold<Item, A> {
+    pub fn new(
+        init: A,
+        add: impl Fn(&A, &Item) -> A + Send + Sync + 'static,
+        remove: impl Fn(&A, &Item) -> Option<A> + Send + Sync + 'static,
+    ) -> Self {
+        Fold {
+            acc: init.clone(),
+            init,
+            add: Box::new(add),
+            remove: Box::new(remove),
+        }
+    }
+
+    pub fn get(&self) -> A {
+        self.acc.clone()
+    }
+
+    fn insert(&mut self, item: &Item) {
+        self.acc = (self.add)(&self.acc, item);
+    }
+
+    /// returns false if the aggregate has to be recomputed
+    fn remove(&mut self, item: &Item) -> bool {
+        match (self.remove)(&self.acc, item) {
+            Some(acc) => {
+                self.acc = acc;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update(&mut self, old: &Item, new: &Item) -> bool {
+        if self.remove(old) {
+            self.insert(new);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn recompute<'a>(&mut self, items: impl IntoIterator<Item = &'a Item>)
+    where
+        Item: 'a,
+    {
+        self.acc = self.init.clone();
+        for item in items {
+            self.insert(item);
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Views which can be reduced into a singleton
+pub trait Reducible: View
+where
+    Self::Msg: Clone,
+{
+    type Item: Clone + Send + Sync + 'static;
+
+    fn reduce<A: Clone + Send + Sync + 'static>(
+        port: &OuterViewPort<Self>,
+        fold: Fold<Self::Item, A>,
+    ) -> OuterViewPort<dyn SingletonView<Item = A>>;
+}
+
+impl<V: Reducible + ?Sized> OuterViewPort<V>
+where
+    V::Msg: Clone,
+{
+    /// `inverse` has to undo `f`, i.e. `inverse(f(a, x), x) == a`
+    pub fn fold<A: Clone + Send + Sync + 'static>(
+        &self,
+        init: A,
+        f: impl Fn(&A, &V::Item) -> A + Send + Sync + 'static,
+        inverse: impl Fn(&A, &V::Item) -> A + Send + Sync + 'static,
+    ) -> OuterViewPort<dyn SingletonView<Item = A>> {
+        V::reduce(self, Fold::new(init, f, move |acc, x| Some(inverse(acc, x))))
+    }
+
+    pub fn count(&self) -> OuterViewPort<dyn SingletonView<Item = usize>> {
+        self.fold(0, |n, _| n + 1, |n, _| n - 1)
+    }
+
+    pub fn sum(&self) -> OuterViewPort<dyn SingletonView<Item = V::Item>>
+    where
+        V::Item: Default + Add<Output = V::Item> + Sub<Output = V::Item>,
+    {
+        self.fold(
+            V::Item::default(),
+            |sum, x| sum.clone() + x.clone(),
+            |sum, x| sum.clone() - x.clone(),
+        )
+    }
+
+    pub fn min(&self) -> OuterViewPort<dyn SingletonView<Item = Option<V::Item>>>
+    where
+        V::Item: Ord,
+    {
+        V::reduce(self, Fold::new(
+            None,
+            |min: &Option<V::Item>, x| match min {
+                Some(min) if min <= x => Some(min.clone()),
+                _ => Some(x.clone()),
+            },
+            |min, x| if min.as_ref() == Some(x) { None } else { Some(min.clone()) },
+        ))
+    }
+
+    pub fn max(&self) -> OuterViewPort<dyn SingletonView<Item = Option<V::Item>>>
+    where
+        V::Item: Ord,
+    {
+        V::reduce(self, Fold::new(
+            None,
+            |max: &Option<V::Item>, x| match max {
+                Some(max) if max >= x => Some(max.clone()),
+                _ => Some(x.clone()),
+            },
+            |max, x| if max.as_ref() == Some(x) { None } else { Some(max.clone()) },
+        ))
+    }
+
+    /// true if at least one item satisfies `pred`
+    pub fn any(
+        &self,
+        pred: impl Fn(&V::Item) -> bool + Send + Sync + 'static,
+    ) -> OuterViewPort<dyn SingletonView<Item = bool>> {
+        self.count_matching(pred).map(|n| n > 0)
+    }
+
+    /// true if every item satisfies `pred`
+    pub fn all(
+        &self,
+        pred: impl Fn(&V::Item) -> bool + Send + Sync + 'static,
+    ) -> OuterViewPort<dyn SingletonView<Item = bool>> {
+        self.count_matching(move |x| !pred(x)).map(|n| n == 0)
+    }
+
+    fn count_matching(
+        &self,
+        pred: impl Fn(&V::Item) -> bool + Send + Sync + 'static,
+    ) -> OuterViewPort<dyn SingletonView<Item = usize>> {
+        let pred = Arc::new(pred);
+        let inv_pred = pred.clone();
+        self.fold(
+            0,
+            move |n, x| if pred(x) { n + 1 } else { *n },
+            move |n, x| if inv_pred(x) { n - 1 } else { *n },
+        )
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Singleton holding the aggregate of a source view.
+/// `M` is the copy of the source items, which is needed
+/// to know the old value of removed or changed items.
+/// Sequences also keep the messages of the running update in `M`,
+/// since the changed items can only be read once it is complete.
+pub struct Reduce<SrcView: ?Sized, M, Item, A> {
+    src_view: Option<Arc<SrcView>>,
+    items: M,
+    fold: Fold<Item, A>,
+    cast: Arc<RwLock<ObserverBroadcast<dyn SingletonView<Item = A>>>>,
+}
+
+impl<SrcView, M, Item, A> Reduce<SrcView, M, Item, A>
+where
+    SrcView: View + ?Sized + 'static,
+    SrcView::Msg: Clone,
+    M: Default + Send + Sync + 'static,
+    Item: 'static,
+    A: Clone + Send + Sync + 'static,
+{
+    fn attach(port: &OuterViewPort<SrcView>, fold: Fold<Item, A>) -> OuterViewPort<dyn SingletonView<Item = A>>
+    where
+        Self: Observer<SrcView>,
+    {
+        let out = ViewPort::new();
+        out.add_update_hook(Arc::new(port.0.clone()));
+
+        let reduce = Arc::new(RwLock::new(Reduce {
+            src_view: None,
+            items: M::default(),
+            fold,
+            cast: out.inner().get_broadcast(),
+        }));
+
+        port.add_observer(reduce.clone());
+        out.inner().set_view(Some(reduce));
+        out.into_outer()
+    }
+}
+
+impl<SrcView, M, Item, A> View for Reduce<SrcView, M, Item, A>
+where
+    SrcView: ?Sized + Send + Sync,
+    M: Send + Sync,
+    Item: 'static,
+    A: Clone + Send + Sync + 'static,
+{
+    type Msg = ();
+}
+
+impl<SrcView, M, Item, A> SingletonView for Reduce<SrcView, M, Item, A>
+where
+    SrcView: ?Sized + Send + Sync,
+    M: Send + Sync,
+    Item: 'static,
+    A: Clone + Send + Sync + 'static,
+{
+    type Item = A;
+
+    fn get(&self) -> A {
+        self.fold.get()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item> Reducible for dyn ListView<Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    type Item = Item;
+
+    fn reduce<A: Clone + Send + Sync + 'static>(
+        port: &OuterViewPort<Self>,
+        fold: Fold<Item, A>,
+    ) -> OuterViewPort<dyn SingletonView<Item = A>> {
+        Reduce::<Self, Vec<Item>, Item, A>::attach(port, fold)
+    }
+}
+
+impl<Item, A> Observer<dyn ListView<Item>> for Reduce<dyn ListView<Item>, Vec<Item>, Item, A>
+where
+    Item: Clone + Send + Sync + 'static,
+    A: Clone + Send + Sync + 'static,
+{
+    fn reset(&mut self, view: Option<Arc<dyn ListView<Item>>>) {
+        self.items = view.map(|v| v.iter().collect()).unwrap_or_default();
+        self.fold.recompute(self.items.iter());
+        self.cast.notify(&());
+    }
+
+    fn notify(&mut self, diff: &ListDiff<Item>) {
+        match diff {
+            ListDiff::Clear => {
+                self.items.clear();
+                self.fold.recompute(self.items.iter());
+            }
+            ListDiff::Remove(idx) => {
+                let old = self.items.remove(*idx);
+                if !self.fold.remove(&old) {
+                    self.fold.recompute(self.items.iter());
+                }
+            }
+            ListDiff::Insert{ idx, val } => {
+                self.items.insert(*idx, val.clone());
+                self.fold.insert(val);
+            }
+            ListDiff::Update{ idx, val } => {
+                let old = std::mem::replace(&mut self.items[*idx], val.clone());
+                if !self.fold.update(&old, val) {
+                    self.fold.recompute(self.items.iter());
+                }
+            }
+        }
+        self.cast.notify(&());
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item> Reducible for dyn SequenceView<Item = Item>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    type Item = Item;
+
+    fn reduce<A: Clone + Send + Sync + 'static>(
+        port: &OuterViewPort<Self>,
+        fold: Fold<Item, A>,
+    ) -> OuterViewPort<dyn SingletonView<Item = A>> {
+        Reduce::<Self, (Vec<Item>, SeqDiffBatch), Item, A>::attach(port, fold)
+    }
+}
+
+impl<Item, A> Observer<dyn SequenceView<Item = Item>>
+    for Reduce<dyn SequenceView<Item = Item>, (Vec<Item>, SeqDiffBatch), Item, A>
+where
+    Item: Clone + Send + Sync + 'static,
+    A: Clone + Send + Sync + 'static,
+{
+    fn reset(&mut self, view: Option<Arc<dyn SequenceView<Item = Item>>>) {
+        self.items = (view.as_ref().map(|v| v.iter().collect()).unwrap_or_default(), SeqDiffBatch::default());
+        self.src_view = view;
+
+        self.fold.recompute(self.items.0.iter());
+        self.cast.notify(&());
+    }
+
+    fn notify(&mut self, diff: &SeqDiff) {
+        self.items.1.push(diff.clone());
+    }
+
+    fn end_update(&mut self) {
+        let (src_view, (items, pending)) = (&self.src_view, &mut self.items);
+
+        let mut consistent = true;
+        for diff in std::mem::take(pending) {
+            match diff {
+                SeqDiff::Range(r) => {
+                    for idx in r {
+                        if let Some(val) = src_view.get(&idx) {
+                            let old = std::mem::replace(&mut items[idx], val);
+                            consistent &= self.fold.update(&old, &items[idx]);
+                        }
+                    }
+                }
+                SeqDiff::Shift { at, by } if by > 0 => {
+                    let new_items: Vec<Item> = (at..at + by as usize)
+                        .filter_map(|idx| src_view.get(&idx))
+                        .collect();
+                    for val in new_items.iter() {
+                        self.fold.insert(val);
+                    }
+                    items.splice(at..at, new_items);
+                }
+                SeqDiff::Shift { at, by } => {
+                    for old in items.drain(at..at + by.unsigned_abs()) {
+                        consistent &= self.fold.remove(&old);
+                    }
+                }
+            }
+        }
+
+        if !consistent {
+            self.fold.recompute(items.iter());
+        }
+        self.cast.notify(&());
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Key, Item> Reducible for dyn IndexView<Key, Item = Item>
+where
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    Item: Clone + Send + Sync + 'static,
+{
+    type Item = Item;
+
+    fn reduce<A: Clone + Send + Sync + 'static>(
+        port: &OuterViewPort<Self>,
+        fold: Fold<Item, A>,
+    ) -> OuterViewPort<dyn SingletonView<Item = A>> {
+        Reduce::<Self, HashMap<Key, Item>, Item, A>::attach(port, fold)
+    }
+}
+
+/// `IndexArea::Full` is resolved to the keys of the current area and all keys seen before.
+/// Ranges are enumerated by `RangeKeys`, an area which can not be enumerated panics,
+/// since the result would silently miss items.
+impl<Key, Item, A> Observer<dyn IndexView<Key, Item = Item>>
+    for Reduce<dyn IndexView<Key, Item = Item>, HashMap<Key, Item>, Item, A>
+where
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    Item: Clone + Send + Sync + 'static,
+    A: Clone + Send + Sync + 'static,
+{
+    fn reset(&mut self, view: Option<Arc<dyn IndexView<Key, Item = Item>>>) {
+        self.src_view = view;
+        self.items.clear();
+
+        for key in area_keys(&self.src_view.area()) {
+            if let Some(val) = self.src_view.get(&key) {
+                self.items.insert(key, val);
+            }
+        }
+
+        self.fold.recompute(self.items.values());
+        self.cast.notify(&());
+    }
+
+    fn notify(&mut self, area: &IndexArea<Key>) {
+        let (src_view, items) = (&self.src_view, &mut self.items);
+
+        let keys: Vec<Key> = match area {
+            IndexArea::Empty => vec![],
+            IndexArea::Full => {
+                let mut keys: Vec<Key> = items.keys().cloned().collect();
+                let current = area_keys(&src_view.area());
+                keys.extend(current.into_iter().filter(|key| !items.contains_key(key)));
+                keys
+            }
+            area => area_keys(area),
+        };
+
+        let mut consistent = true;
+        for key in keys {
+            match (items.get_mut(&key), src_view.get(&key)) {
+                (Some(old), Some(new)) => {
+                    let old = std::mem::replace(old, new.clone());
+                    consistent &= self.fold.update(&old, &new);
+                }
+                (Some(_), None) => {
+                    let old = items.remove(&key).unwrap();
+                    consistent &= self.fold.remove(&old);
+                }
+                (None, Some(new)) => {
+                    self.fold.insert(&new);
+                    items.insert(key, new);
+                }
+                (None, None) => {}
+            }
+        }
+
+        if !consistent {
+            self.fold.recompute(items.values());
+        }
+        self.cast.notify(&());
+    }
+}
+
+fn area_keys<Key: Clone>(area: &IndexArea<Key>) -> Vec<Key> {
+    area.keys().expect("can not enumerate the keys of an index area")
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            buffer::{grid_hashmap::GridBuffer, index_hashmap::*, vec::*},
+            view::{port::UpdateTask, singleton::SingletonView},
+        },
+        cgmath::Point2,
+    };
+
+    #[test]
+    fn reduce_list() {
+        let mut buffer = VecBuffer::<i32>::with_data(vec![3, 1, 4]);
+        let list = buffer.get_port().to_list();
+
+        let count = list.count();
+        let sum = list.sum();
+        let min = list.min();
+        let max = list.max();
+        let any_even = list.any(|x| x % 2 == 0);
+        let all_pos = list.all(|x| *x > 0);
+
+        assert_eq!(count.get_view().unwrap().get(), 3);
+        assert_eq!(sum.get_view().unwrap().get(), 8);
+        assert_eq!(min.get_view().unwrap().get(), Some(1));
+        assert_eq!(max.get_view().unwrap().get(), Some(4));
+        assert!(any_even.get_view().unwrap().get());
+
+        buffer.remove(1);       // 1, the current minimum
+        buffer.update(1, 5);    // 4 -> 5
+        buffer.push(-2);
+        count.0.update();
+        sum.0.update();
+        min.0.update();
+        max.0.update();
+        any_even.0.update();
+        all_pos.0.update();
+
+        assert_eq!(count.get_view().unwrap().get(), 3);
+        assert_eq!(sum.get_view().unwrap().get(), 6);
+        assert_eq!(min.get_view().unwrap().get(), Some(-2));
+        assert_eq!(max.get_view().unwrap().get(), Some(5));
+        assert!(any_even.get_view().unwrap().get());
+        assert!(!all_pos.get_view().unwrap().get());
+
+        buffer.clear();
+        min.0.update();
+        sum.0.update();
+        assert_eq!(min.get_view().unwrap().get(), None);
+        assert_eq!(sum.get_view().unwrap().get(), 0);
+    }
+
+    #[test]
+    fn reduce_sequence() {
+        let mut buffer = VecBuffer::with_data(vec![2, 7]);
+        let seq = buffer.get_port().to_sequence();
+        let max = seq.max();
+        let product = seq.fold(1.0, |p, x| p * *x as f64, |p, x| p / *x as f64);
+
+        buffer.push(5);
+        buffer.update(1, 1);
+        max.0.update();
+        product.0.update();
+        assert_eq!(max.get_view().unwrap().get(), Some(5));
+        assert_eq!(product.get_view().unwrap().get(), 10.0);
+
+        buffer.remove(2);
+        max.0.update();
+        assert_eq!(max.get_view().unwrap().get(), Some(2));
+    }
+
+    #[test]
+    fn reduce_index() {
+        let mut index = IndexBuffer::<char, u32>::new();
+        index.insert('a', 10);
+        let total = index.get_port().sum();
+        assert_eq!(total.get_view().unwrap().get(), 10);
+
+        index.insert('b', 5);
+        index.insert('a', 1);
+        total.0.update();
+        assert_eq!(total.get_view().unwrap().get(), 6);
+
+        index.remove('b');
+        total.0.update();
+        assert_eq!(total.get_view().unwrap().get(), 1);
+
+        // grids report their area as a range
+        let mut grid: GridBuffer<u32> = vec![(Point2::new(0, 0), 3), (Point2::new(1, 2), 4)].into_iter().collect();
+        let total = grid.get_port().sum();
+        assert_eq!(total.get_view().unwrap().get(), 7);
+
+        grid.insert(Point2::new(3, 0), 1);
+        total.0.update();
+        assert_eq!(total.get_view().unwrap().get(), 8);
+    }
+}
@@ -0,0 +1,120 @@
+use {
+    crate::{
+        view::{
+            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            list::{ListDiff, ListView},
+        },
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> OuterViewPort<dyn ListView<Item>> {
+    pub fn rev(&self) -> OuterViewPort<dyn ListView<Item>> {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let rev = Arc::new(RwLock::new(RevList {
+            src_view: None,
+            cur_len: 0,
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(rev.clone());
+        port.inner().set_view(Some(rev));
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct RevList<Item: Clone + Send + Sync + 'static> {
+    src_view: Option<Arc<dyn ListView<Item>>>,
+
+    /// length as of the last notification,
+    /// needed to mirror the notified indices
+    cur_len: usize,
+    cast: Arc<RwLock<ObserverBroadcast<dyn ListView<Item>>>>,
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> View for RevList<Item> {
+    type Msg = ListDiff<Item>;
+}
+
+impl<Item: Clone + Send + Sync + 'static> ListView<Item> for RevList<Item> {
+    fn len(&self) -> Option<usize> {
+        self.src_view.len()
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        let len = self.src_view.len()?;
+        self.src_view.get(&len.checked_sub(idx + 1)?)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> Observer<dyn ListView<Item>> for RevList<Item> {
+    fn reset(&mut self, view: Option<Arc<dyn ListView<Item>>>) {
+        if self.cur_len > 0 {
+            self.cast.notify(&ListDiff::Clear);
+        }
+
+        self.src_view = view;
+        self.cur_len = self.src_view.len().unwrap_or(0);
+        for idx in 0..self.cur_len {
+            if let Some(val) = self.get(&idx) {
+                self.cast.notify(&ListDiff::Insert{ idx, val });
+            }
+        }
+    }
+
+    fn notify(&mut self, diff: &ListDiff<Item>) {
+        let len = self.cur_len;
+        let reversed = match diff {
+            ListDiff::Clear => {
+                self.cur_len = 0;
+                ListDiff::Clear
+            }
+            ListDiff::Remove(idx) => {
+                self.cur_len -= 1;
+                ListDiff::Remove(len - 1 - idx)
+            }
+            ListDiff::Insert{ idx, val } => {
+                self.cur_len += 1;
+                ListDiff::Insert{ idx: len - idx, val: val.clone() }
+            }
+            ListDiff::Update{ idx, val } => ListDiff::Update{ idx: len - 1 - idx, val: val.clone() },
+        };
+        self.cast.notify(&reversed);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::port::UpdateTask;
+
+    #[test]
+    fn rev_list1() {
+        let mut buffer = VecBuffer::with_data(vec!['a', 'b', 'c']);
+        let target_port = buffer.get_port().to_list().rev();
+
+        let copy = VecBuffer::<char>::new();
+        let _keepalive = copy.attach_to(target_port.clone());
+        assert_eq!(*copy.get_port().get_view().unwrap().read().unwrap(), vec!['c', 'b', 'a']);
+
+        buffer.insert(1, 'x');
+        buffer.update(0, 'A');
+        buffer.remove(3);
+        target_port.0.update();
+        copy.get_port().0.update();
+        assert_eq!(*copy.get_port().get_view().unwrap().read().unwrap(), vec!['b', 'x', 'A']);
+    }
+}
@@ -0,0 +1,119 @@
+use {
+    crate::{
+        view::{
+            Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
+            sequence::{SeqDiff, SequenceView},
+        },
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: 'static> OuterViewPort<dyn SequenceView<Item = Item>> {
+    pub fn rev(&self) -> OuterViewPort<dyn SequenceView<Item = Item>> {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let rev = Arc::new(RwLock::new(RevSequence {
+            src_view: None,
+            cur_len: 0,
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(rev.clone());
+        port.inner().set_view(Some(rev));
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct RevSequence<Item: 'static> {
+    src_view: Option<Arc<dyn SequenceView<Item = Item>>>,
+
+    /// length as of the last notification,
+    /// needed to mirror the notified indices
+    cur_len: usize,
+    cast: Arc<RwLock<ObserverBroadcast<dyn SequenceView<Item = Item>>>>,
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: 'static> View for RevSequence<Item> {
+    type Msg = SeqDiff;
+}
+
+impl<Item: 'static> SequenceView for RevSequence<Item> {
+    type Item = Item;
+
+    fn len(&self) -> Option<usize> {
+        self.src_view.len()
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        let len = self.src_view.len()?;
+        self.src_view.get(&len.checked_sub(idx + 1)?)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: 'static> Observer<dyn SequenceView<Item = Item>> for RevSequence<Item> {
+    fn reset(&mut self, view: Option<Arc<dyn SequenceView<Item = Item>>>) {
+        let old_len = self.cur_len;
+        self.src_view = view;
+        self.cur_len = self.src_view.len().unwrap_or(0);
+
+        self.cast.notify_each(SeqDiff::replace(old_len, self.cur_len));
+    }
+
+    fn notify(&mut self, diff: &SeqDiff) {
+        let len = self.cur_len;
+        self.cur_len = diff.apply_len(len);
+
+        self.cast.notify(&match diff {
+            SeqDiff::Range(r) => SeqDiff::Range(len - r.end..len - r.start),
+            SeqDiff::Shift { at, by } if *by > 0 => SeqDiff::Shift { at: len - at, by: *by },
+            SeqDiff::Shift { at, by } => SeqDiff::Shift { at: len - at - by.unsigned_abs(), by: *by },
+        });
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::{port::UpdateTask, sequence::{SeqDiff, SequenceViewExt}};
+
+    #[test]
+    fn rev_seq1() {
+        let mut buffer = VecBuffer::with_data(vec!['a', 'b', 'c']);
+        let target_port = buffer.get_port().to_sequence().rev();
+        let target_view = target_port.get_view().unwrap();
+        assert_eq!(target_view.iter().collect::<String>(), "cba");
+
+        let notified = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _obs = target_port.add_notify_fn({
+            let notified = notified.clone();
+            move |diff| notified.lock().unwrap().push(diff.clone())
+        });
+
+        buffer.insert(1, 'x');
+        buffer.update(0, 'A');
+        buffer.remove(3);
+        target_port.0.update();
+
+        assert_eq!(target_view.iter().collect::<String>(), "bxA");
+        assert_eq!(
+            *notified.lock().unwrap(),
+            vec![
+                SeqDiff::Shift { at: 2, by: 1 },
+                SeqDiff::Range(3..4),
+                SeqDiff::Shift { at: 0, by: -1 },
+            ]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use {
             InnerViewPort, Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
             grid::GridView,
             index::{IndexArea, IndexView},
-            sequence::SequenceView,
+            sequence::{SeqDiff, SequenceView},
         }
     },
     std::sync::Arc,
@@ -95,7 +95,10 @@ where
         self.cast.notify(&self.area());
     }
 
-    fn notify(&mut self, idx: &usize) {
-        self.cast.notify(&IndexArea::Set(vec![*idx]));
+    fn notify(&mut self, diff: &SeqDiff) {
+        let range = diff.range(self.src_view.len().unwrap_or(0));
+        if !range.is_empty() {
+            self.cast.notify(&IndexArea::Range(range.start..=range.end - 1));
+        }
     }
 }
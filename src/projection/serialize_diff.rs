@@ -0,0 +1,520 @@
+use {
+    crate::{
+        buffer::{
+            index_hashmap::IndexBuffer,
+            singleton::SingletonBuffer,
+            vec::{VecBuffer, VecDiff},
+        },
+        view::{
+            index::{IndexArea, IndexView},
+            list::{ListDiff, ListView},
+            sequence::{SeqDiff, SequenceView},
+            singleton::SingletonView,
+            View, ViewSnapshot,
+        },
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        cmp::Ordering,
+        collections::HashSet,
+        fmt,
+        hash::Hash,
+        sync::{Arc, RwLock},
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Translates the messages of a view into self-contained diffs,
+/// which carry all data required to replicate the view elsewhere.
+pub trait DiffEncoder: Send + Sync {
+    type View: View + ?Sized;
+    type Diff;
+
+    /// diffs which replace the previously encoded view with `view`
+    fn reset(&mut self, view: Option<Arc<Self::View>>) -> Result<Vec<Self::Diff>, SerializeError>;
+
+    /// diffs for a single message of the current view
+    fn encode(&mut self, msg: &<Self::View as View>::Msg) -> Result<Vec<Self::Diff>, SerializeError>;
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Error of a serialization observer.
+/// After the first error, a writer stops writing.
+#[derive(Debug)]
+pub enum SerializeError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Bincode(bincode::Error),
+
+    /// the area of an index view can not be enumerated,
+    /// so its items can not be encoded
+    Area,
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SerializeError::Io(err) => write!(f, "error writing diff: {}", err),
+            SerializeError::Json(err) => write!(f, "error encoding diff as json: {}", err),
+            SerializeError::Bincode(err) => write!(f, "error encoding diff as bincode: {}", err),
+            SerializeError::Area => write!(f, "area of index view can not be enumerated"),
+        }
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl From<std::io::Error> for SerializeError {
+    fn from(err: std::io::Error) -> Self {
+        SerializeError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SerializeError {
+    fn from(err: serde_json::Error) -> Self {
+        SerializeError::Json(err)
+    }
+}
+
+impl From<bincode::Error> for SerializeError {
+    fn from(err: bincode::Error) -> Self {
+        SerializeError::Bincode(err)
+    }
+}
+
+/// Error while reading serialized diffs.
+/// `line` and `frame` count from 1, all diffs before were applied.
+#[derive(Debug)]
+pub enum DiffDecodeError {
+    /// for JSON input, `frame` is the line number
+    Io { frame: usize, error: std::io::Error },
+    Json { line: usize, error: serde_json::Error },
+    Bincode { frame: usize, error: bincode::Error },
+
+    /// the input ended in the middle of a frame
+    Truncated { frame: usize },
+}
+
+impl fmt::Display for DiffDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffDecodeError::Io { frame, error } => {
+                write!(f, "error reading diff {}: {}", frame, error)
+            }
+            DiffDecodeError::Json { line, error } => {
+                write!(f, "error parsing json in line {}: {}", line, error)
+            }
+            DiffDecodeError::Bincode { frame, error } => {
+                write!(f, "error parsing bincode in frame {}: {}", frame, error)
+            }
+            DiffDecodeError::Truncated { frame } => write!(f, "frame {} is truncated", frame),
+        }
+    }
+}
+
+impl std::error::Error for DiffDecodeError {}
+
+/// Callback of a writer which is invoked on the first error
+pub type ErrorCallback = Box<dyn FnMut(&SerializeError) + Send + Sync>;
+
+/// First error of a writer, which stops writing after it
+#[derive(Default)]
+pub struct WriteError {
+    error: Option<SerializeError>,
+    on_error: Option<ErrorCallback>,
+}
+
+impl WriteError {
+    pub fn new(on_error: Option<ErrorCallback>) -> Self {
+        WriteError { error: None, on_error }
+    }
+
+    pub fn get(&self) -> Option<&SerializeError> {
+        self.error.as_ref()
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// keep the error of `result` and pass it to the callback
+    pub fn handle(&mut self, result: Result<(), SerializeError>) {
+        if let Err(err) = result {
+            if let Some(on_error) = self.on_error.as_mut() {
+                on_error(&err);
+            }
+            self.error = Some(err);
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Sequence diff: the new value at `idx`,
+/// or `None` if the sequence now ends before `idx`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SequenceUpdate<T> {
+    pub idx: usize,
+    pub val: Option<T>,
+}
+
+/// Index diff: the notified area and the new values of all keys
+/// inside of it that are known to the encoder (`None` if removed)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndexUpdate<Key, T> {
+    pub area: IndexArea<Key>,
+    pub items: Vec<(Key, Option<T>)>,
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct SingletonEncoder<T> {
+    view: Option<Arc<dyn SingletonView<Item = T>>>,
+}
+
+impl<T> Default for SingletonEncoder<T> {
+    fn default() -> Self {
+        SingletonEncoder { view: None }
+    }
+}
+
+impl<T> DiffEncoder for SingletonEncoder<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type View = dyn SingletonView<Item = T>;
+    type Diff = T;
+
+    fn reset(&mut self, view: Option<Arc<Self::View>>) -> Result<Vec<T>, SerializeError> {
+        self.view = view;
+        self.encode(&())
+    }
+
+    fn encode(&mut self, _msg: &()) -> Result<Vec<T>, SerializeError> {
+        Ok(self.view.iter().map(|v| v.get()).collect())
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct ListEncoder<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    view: Option<Arc<dyn ListView<T>>>,
+}
+
+impl<T> Default for ListEncoder<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        ListEncoder { view: None }
+    }
+}
+
+impl<T> DiffEncoder for ListEncoder<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type View = dyn ListView<T>;
+    type Diff = ListDiff<T>;
+
+    fn reset(&mut self, view: Option<Arc<Self::View>>) -> Result<Vec<ListDiff<T>>, SerializeError> {
+        let old_view = std::mem::replace(&mut self.view, view.clone());
+        Ok(<dyn ListView<T>>::snapshot(old_view, view))
+    }
+
+    fn encode(&mut self, diff: &ListDiff<T>) -> Result<Vec<ListDiff<T>>, SerializeError> {
+        Ok(vec![diff.clone()])
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct VecEncoder<T> {
+    view: Option<Arc<RwLock<Vec<T>>>>,
+}
+
+impl<T> Default for VecEncoder<T> {
+    fn default() -> Self {
+        VecEncoder { view: None }
+    }
+}
+
+impl<T> DiffEncoder for VecEncoder<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type View = RwLock<Vec<T>>;
+    type Diff = VecDiff<T>;
+
+    fn reset(&mut self, view: Option<Arc<Self::View>>) -> Result<Vec<VecDiff<T>>, SerializeError> {
+        let old_view = std::mem::replace(&mut self.view, view.clone());
+        Ok(RwLock::<Vec<T>>::snapshot(old_view, view))
+    }
+
+    fn encode(&mut self, diff: &VecDiff<T>) -> Result<Vec<VecDiff<T>>, SerializeError> {
+        Ok(vec![diff.clone()])
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Sequence messages may arrive in any order,
+/// so the encoder keeps track of the length known to the receiver
+/// and never sends a value behind it without filling the gap first.
+pub struct SequenceEncoder<T> {
+    view: Option<Arc<dyn SequenceView<Item = T>>>,
+    len: usize,
+}
+
+impl<T> Default for SequenceEncoder<T> {
+    fn default() -> Self {
+        SequenceEncoder { view: None, len: 0 }
+    }
+}
+
+impl<T> SequenceEncoder<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn update(&self, idx: usize) -> SequenceUpdate<T> {
+        SequenceUpdate {
+            idx,
+            val: self.view.get(&idx),
+        }
+    }
+}
+
+impl<T> DiffEncoder for SequenceEncoder<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type View = dyn SequenceView<Item = T>;
+    type Diff = SequenceUpdate<T>;
+
+    fn reset(&mut self, view: Option<Arc<Self::View>>) -> Result<Vec<SequenceUpdate<T>>, SerializeError> {
+        self.view = view;
+        let new_len = self.view.len().unwrap_or(0);
+
+        let mut diffs: Vec<_> = (0..new_len).map(|idx| self.update(idx)).collect();
+        if new_len < self.len {
+            diffs.push(SequenceUpdate { idx: new_len, val: None });
+        }
+
+        self.len = new_len;
+        Ok(diffs)
+    }
+
+    /// shifts are sent as updates of all moved items
+    fn encode(&mut self, diff: &SeqDiff) -> Result<Vec<SequenceUpdate<T>>, SerializeError> {
+        let new_len = self.view.len().unwrap_or(0);
+        let range = diff.range(new_len);
+        let mut diffs = Vec::new();
+
+        let end = std::cmp::min(range.end, new_len);
+        if range.start < end {
+            let begin = std::cmp::min(range.start, self.len);
+            diffs.extend((begin..end).map(|i| self.update(i)));
+            self.len = std::cmp::max(self.len, end);
+        }
+
+        if new_len < self.len {
+            self.len = new_len;
+            diffs.push(SequenceUpdate { idx: new_len, val: None });
+        }
+        Ok(diffs)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// `IndexArea::Full` is resolved to the keys of the current area
+/// and all keys sent before, so removed keys are sent as well.
+/// Ranges are enumerated by `RangeKeys`, an area which can not
+/// be enumerated is an error.
+pub struct IndexEncoder<Key, T>
+where
+    Key: Send + Sync,
+{
+    view: Option<Arc<dyn IndexView<Key, Item = T>>>,
+    keys: HashSet<Key>,
+}
+
+impl<Key, T> Default for IndexEncoder<Key, T>
+where
+    Key: Send + Sync,
+{
+    fn default() -> Self {
+        IndexEncoder {
+            view: None,
+            keys: HashSet::new(),
+        }
+    }
+}
+
+impl<Key, T> DiffEncoder for IndexEncoder<Key, T>
+where
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    type View = dyn IndexView<Key, Item = T>;
+    type Diff = IndexUpdate<Key, T>;
+
+    fn reset(&mut self, view: Option<Arc<Self::View>>) -> Result<Vec<IndexUpdate<Key, T>>, SerializeError> {
+        self.view = view;
+        self.encode(&IndexArea::Full)
+    }
+
+    fn encode(&mut self, area: &IndexArea<Key>) -> Result<Vec<IndexUpdate<Key, T>>, SerializeError> {
+        let keys: Vec<Key> = match area {
+            IndexArea::Empty => return Ok(vec![]),
+            IndexArea::Full => {
+                let mut keys = self.keys.clone();
+                keys.extend(self.view.area().keys().ok_or(SerializeError::Area)?);
+                keys.into_iter().collect()
+            }
+            area => area.keys().ok_or(SerializeError::Area)?,
+        };
+
+        let items: Vec<_> = keys
+            .into_iter()
+            .map(|key| {
+                let val = self.view.get(&key);
+                if val.is_some() {
+                    self.keys.insert(key.clone());
+                } else {
+                    self.keys.remove(&key);
+                }
+                (key, val)
+            })
+            .collect();
+
+        Ok(vec![IndexUpdate {
+            area: area.clone(),
+            items,
+        }])
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Buffers which can replicate a view from its encoded diffs
+pub trait ApplyDiff<Diff> {
+    fn apply(&mut self, diff: Diff);
+}
+
+impl<T> ApplyDiff<VecDiff<T>> for VecBuffer<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn apply(&mut self, diff: VecDiff<T>) {
+        self.apply_diff(diff);
+    }
+}
+
+impl<T> ApplyDiff<T> for SingletonBuffer<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn apply(&mut self, val: T) {
+        self.set(val);
+    }
+}
+
+impl<T> ApplyDiff<ListDiff<T>> for VecBuffer<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn apply(&mut self, diff: ListDiff<T>) {
+        self.apply_diff(match diff {
+            ListDiff::Clear => VecDiff::Clear,
+            ListDiff::Remove(idx) => VecDiff::Remove(idx),
+            ListDiff::Insert { idx, val } => VecDiff::Insert { idx, val },
+            ListDiff::Update { idx, val } => VecDiff::Update { idx, val },
+        });
+    }
+}
+
+impl<T> ApplyDiff<SequenceUpdate<T>> for VecBuffer<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn apply(&mut self, diff: SequenceUpdate<T>) {
+        match diff.val {
+            Some(val) => match diff.idx.cmp(&self.len()) {
+                Ordering::Less => self.update(diff.idx, val),
+                Ordering::Equal => self.push(val),
+                // the encoder never skips indices
+                Ordering::Greater => {}
+            },
+            None => {
+                while self.len() > diff.idx {
+                    self.remove(self.len() - 1);
+                }
+            }
+        }
+    }
+}
+
+impl<Key, T> ApplyDiff<IndexUpdate<Key, T>> for IndexBuffer<Key, T>
+where
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    fn apply(&mut self, diff: IndexUpdate<Key, T>) {
+        for (key, val) in diff.items {
+            self.update(key, val);
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            buffer::grid_hashmap::GridBuffer,
+            projection::serialize_diff::*,
+        },
+        cgmath::Point2,
+    };
+
+    #[test]
+    fn index_encoder_range_area() {
+        let grid: GridBuffer<char> = vec![
+            (Point2::new(0, 0), 'a'),
+            (Point2::new(1, 1), 'b'),
+        ].into_iter().collect();
+
+        let mut encoder = IndexEncoder::default();
+        let diffs = encoder.reset(grid.get_port().get_view()).unwrap();
+        let mut items: Vec<_> = diffs[0].items.iter()
+            .filter_map(|(key, val)| Some((*key, (*val)?)))
+            .collect();
+        items.sort_by_key(|(key, _)| (key.x, key.y));
+        assert_eq!(items, vec![(Point2::new(0, 0), 'a'), (Point2::new(1, 1), 'b')]);
+
+        // a view without an enumerable area
+        let full: Option<Arc<dyn IndexView<u32, Item = char>>> = Some(Arc::new(RwLock::new(FullArea)));
+        assert!(matches!(IndexEncoder::default().reset(full), Err(SerializeError::Area)));
+    }
+
+    struct FullArea;
+
+    impl View for FullArea {
+        type Msg = IndexArea<u32>;
+    }
+
+    impl IndexView<u32> for FullArea {
+        type Item = char;
+
+        fn get(&self, _key: &u32) -> Option<char> {
+            Some('x')
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use {
     crate::{
         view::{
-            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
-            sequence::{SequenceView},
+            Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
+            sequence::{SeqDiff, SequenceView},
             singleton::SingletonView,
         },
     },
@@ -44,7 +44,7 @@ impl<SrcView> View for Singleton2Sequence<SrcView>
 where
     SrcView: SingletonView + ?Sized,
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<SrcView> SequenceView for Singleton2Sequence<SrcView>
@@ -73,11 +73,13 @@ where
     SrcView: SingletonView + ?Sized,
 {
     fn reset(&mut self, view: Option<Arc<SrcView>>) {
+        let old_len = self.len().unwrap_or(0);
         self.src_view = view;
-        self.cast.notify(&0);
+        let new_len = self.len().unwrap_or(0);
+        self.cast.notify_each(SeqDiff::replace(old_len, new_len));
     }
 
     fn notify(&mut self, _: &()) {
-        self.cast.notify(&0);
+        self.cast.notify(&SeqDiff::Range(0..1));
     }
 }
@@ -0,0 +1,244 @@
+use {
+    crate::{
+        buffer::singleton::SingletonBuffer,
+        view::{
+            InnerViewPort, Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            list::{ListDiff, ListView},
+            singleton::SingletonView,
+        },
+        projection::projection_helper::ProjectionHelper,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::{cmp::{max, min}, ops::Range},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> OuterViewPort<dyn ListView<Item>> {
+    /// window of the source indices in `range`,
+    /// moving the range scrolls the window
+    pub fn slice(
+        &self,
+        range: OuterViewPort<dyn SingletonView<Item = Range<usize>>>,
+    ) -> OuterViewPort<dyn ListView<Item>> {
+        let port = ViewPort::new();
+        SliceList::new(self.clone(), range, port.inner());
+        port.into_outer()
+    }
+
+    /// all items except the first `n`
+    pub fn skip(&self, n: usize) -> OuterViewPort<dyn ListView<Item>> {
+        self.slice(SingletonBuffer::new(n..usize::MAX).get_port())
+    }
+
+    /// the first `n` items
+    pub fn take(&self, n: usize) -> OuterViewPort<dyn ListView<Item>> {
+        self.slice(SingletonBuffer::new(0..n).get_port())
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+type RangeArg = Arc<RwLock<Option<Arc<dyn SingletonView<Item = Range<usize>>>>>>;
+
+/// Keeps a copy of the source list, since items move
+/// into the window when items before or inside it are removed.
+pub struct SliceList<Item: Clone + Send + Sync + 'static> {
+    items: Vec<Item>,
+    range_view: RangeArg,
+
+    /// the window as of the last notification
+    range: Range<usize>,
+
+    cast: Arc<RwLock<ObserverBroadcast<dyn ListView<Item>>>>,
+    proj_helper: ProjectionHelper<usize, Self>,
+}
+
+impl<Item: Clone + Send + Sync + 'static> SliceList<Item> {
+    pub fn new(
+        src_port: OuterViewPort<dyn ListView<Item>>,
+        range_port: OuterViewPort<dyn SingletonView<Item = Range<usize>>>,
+        out_port: InnerViewPort<dyn ListView<Item>>,
+    ) -> Arc<RwLock<Self>> {
+        let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
+
+        proj_helper.new_list_arg(0, src_port, |s: &mut Self, diff| {
+            s.notify_src(diff);
+        });
+
+        let slice = Arc::new(RwLock::new(SliceList {
+            items: Vec::new(),
+            range_view: proj_helper.new_singleton_arg(1, range_port, |s: &mut Self, _msg| {
+                let range = s.range_view.get();
+                s.set_range(range);
+            }),
+            range: 0..0,
+            cast: out_port.get_broadcast(),
+            proj_helper,
+        }));
+
+        slice.write().unwrap().proj_helper.set_proj(&slice);
+        out_port.set_view(Some(slice.clone()));
+        slice
+    }
+
+    /// number of items inside the window
+    fn window_len(&self) -> usize {
+        min(self.range.end, self.items.len()).saturating_sub(self.range.start)
+    }
+
+    /// item at the window position `idx`
+    fn item(&self, idx: usize) -> Item {
+        self.items[self.range.start + idx].clone()
+    }
+
+    fn notify_src(&mut self, diff: &ListDiff<Item>) {
+        let start = self.range.start;
+        let old_len = self.window_len();
+
+        match diff {
+            ListDiff::Clear => {
+                self.items.clear();
+                if old_len > 0 {
+                    self.cast.notify(&ListDiff::Clear);
+                }
+            }
+            ListDiff::Update{ idx, val } => {
+                self.items[*idx] = val.clone();
+                if *idx >= start && *idx < start + old_len {
+                    self.cast.notify(&ListDiff::Update{ idx: *idx - start, val: val.clone() });
+                }
+            }
+            ListDiff::Insert{ idx, val } => {
+                self.items.insert(*idx, val.clone());
+                let new_len = self.window_len();
+
+                // an item inserted before the window pushes its predecessor in
+                let pos = idx.saturating_sub(start);
+                if pos < new_len {
+                    self.cast.notify(&ListDiff::Insert{ idx: pos, val: self.item(pos) });
+                    if old_len == new_len {
+                        self.cast.notify(&ListDiff::Remove(new_len));
+                    }
+                }
+            }
+            ListDiff::Remove(idx) => {
+                self.items.remove(*idx);
+                let new_len = self.window_len();
+
+                let pos = min(idx.saturating_sub(start), old_len);
+                if pos < old_len {
+                    self.cast.notify(&ListDiff::Remove(pos));
+                    if old_len == new_len {
+                        self.cast.notify(&ListDiff::Insert{ idx: new_len - 1, val: self.item(new_len - 1) });
+                    }
+                }
+            }
+        }
+    }
+
+    /// items which stay inside the window are kept
+    fn set_range(&mut self, range: Range<usize>) {
+        let old = self.range.start..self.range.start + self.window_len();
+        self.range = range;
+        let new = self.range.start..self.range.start + self.window_len();
+
+        let (a, b) = (max(old.start, new.start), min(old.end, new.end));
+        if a >= b {
+            if !old.is_empty() {
+                self.cast.notify(&ListDiff::Clear);
+            }
+            for idx in 0..new.len() {
+                self.cast.notify(&ListDiff::Insert{ idx, val: self.item(idx) });
+            }
+            return;
+        }
+
+        for _ in old.start..a {
+            self.cast.notify(&ListDiff::Remove(0));
+        }
+        for idx in 0..a - new.start {
+            self.cast.notify(&ListDiff::Insert{ idx, val: self.item(idx) });
+        }
+
+        let pos = b - new.start;
+        for _ in b..old.end {
+            self.cast.notify(&ListDiff::Remove(pos));
+        }
+        for idx in pos..new.len() {
+            self.cast.notify(&ListDiff::Insert{ idx, val: self.item(idx) });
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> View for SliceList<Item> {
+    type Msg = ListDiff<Item>;
+}
+
+impl<Item: Clone + Send + Sync + 'static> ListView<Item> for SliceList<Item> {
+    fn len(&self) -> Option<usize> {
+        Some(self.window_len())
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        if *idx < self.window_len() {
+            Some(self.item(*idx))
+        } else {
+            None
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::{singleton::*, vec::*};
+    use crate::view::{port::UpdateTask, list::ListViewExt};
+
+    #[test]
+    fn slice_list1() {
+        let mut buffer = VecBuffer::with_data((0..10).collect::<Vec<i32>>());
+        let mut range = SingletonBuffer::new(2..5);
+
+        let window = buffer.get_port().to_list().slice(range.get_port());
+        let copy = VecBuffer::<i32>::new();
+        let _keepalive = copy.attach_to(window.clone());
+
+        let check = |expected: Vec<i32>| {
+            window.0.update();
+            copy.get_port().0.update();
+            assert_eq!(window.get_view().unwrap().iter().collect::<Vec<_>>(), expected);
+            assert_eq!(*copy.get_port().get_view().unwrap().read().unwrap(), expected);
+        };
+        check(vec![2, 3, 4]);
+
+        range.set(3..6);
+        check(vec![3, 4, 5]);
+        range.set(0..2);
+        check(vec![0, 1]);
+        range.set(1..4);
+        check(vec![1, 2, 3]);
+
+        buffer.insert(0, -1);
+        buffer.update(3, 20);
+        check(vec![0, 1, 20]);
+
+        buffer.remove(2);
+        buffer.push(10);
+        check(vec![0, 20, 3]);
+
+        let tail = buffer.get_port().to_list().skip(9);
+        let head = buffer.get_port().to_list().take(2);
+        tail.0.update();
+        head.0.update();
+        assert_eq!(tail.get_view().unwrap().iter().collect::<Vec<_>>(), vec![9, 10]);
+        assert_eq!(head.get_view().unwrap().iter().collect::<Vec<_>>(), vec![-1, 0]);
+
+        buffer.clear();
+        check(vec![]);
+    }
+}
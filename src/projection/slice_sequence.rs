@@ -0,0 +1,242 @@
+use {
+    crate::{
+        buffer::singleton::SingletonBuffer,
+        view::{
+            InnerViewPort, Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
+            sequence::{SeqDiff, SequenceView},
+            singleton::SingletonView,
+        },
+        projection::projection_helper::ProjectionHelper,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::{cmp::{max, min}, ops::Range},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: 'static> OuterViewPort<dyn SequenceView<Item = Item>> {
+    /// window of the source indices in `range`,
+    /// moving the range scrolls the window
+    pub fn slice(
+        &self,
+        range: OuterViewPort<dyn SingletonView<Item = Range<usize>>>,
+    ) -> OuterViewPort<dyn SequenceView<Item = Item>> {
+        let port = ViewPort::new();
+        SliceSequence::new(self.clone(), range, port.inner());
+        port.into_outer()
+    }
+
+    /// all items except the first `n`
+    pub fn skip(&self, n: usize) -> OuterViewPort<dyn SequenceView<Item = Item>> {
+        self.slice(SingletonBuffer::new(n..usize::MAX).get_port())
+    }
+
+    /// the first `n` items
+    pub fn take(&self, n: usize) -> OuterViewPort<dyn SequenceView<Item = Item>> {
+        self.slice(SingletonBuffer::new(0..n).get_port())
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+type RangeArg = Arc<RwLock<Option<Arc<dyn SingletonView<Item = Range<usize>>>>>>;
+
+pub struct SliceSequence<Item: 'static> {
+    src: Arc<RwLock<Option<Arc<dyn SequenceView<Item = Item>>>>>,
+    range_view: RangeArg,
+
+    /// the window and source length as of the last notification
+    range: Range<usize>,
+    src_len: usize,
+
+    cast: Arc<RwLock<ObserverBroadcast<dyn SequenceView<Item = Item>>>>,
+    proj_helper: ProjectionHelper<usize, Self>,
+}
+
+impl<Item: 'static> SliceSequence<Item> {
+    pub fn new(
+        src_port: OuterViewPort<dyn SequenceView<Item = Item>>,
+        range_port: OuterViewPort<dyn SingletonView<Item = Range<usize>>>,
+        out_port: InnerViewPort<dyn SequenceView<Item = Item>>,
+    ) -> Arc<RwLock<Self>> {
+        let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
+
+        let slice = Arc::new(RwLock::new(SliceSequence {
+            src: proj_helper.new_sequence_arg(0, src_port, |s: &mut Self, diff| {
+                s.notify_src(diff);
+            }),
+            range_view: proj_helper.new_singleton_arg(1, range_port, |s: &mut Self, _msg| {
+                let range = s.range_view.get();
+                s.set_range(range);
+            }),
+            range: 0..0,
+            src_len: 0,
+            cast: out_port.get_broadcast(),
+            proj_helper,
+        }));
+
+        slice.write().unwrap().proj_helper.set_proj(&slice);
+        out_port.set_view(Some(slice.clone()));
+        slice
+    }
+
+    /// number of items inside the window
+    fn window_len(&self, src_len: usize) -> usize {
+        min(self.range.end, src_len).saturating_sub(self.range.start)
+    }
+
+    fn notify_src(&mut self, diff: &SeqDiff) {
+        let start = self.range.start;
+        let old_len = self.window_len(self.src_len);
+        self.src_len = diff.apply_len(self.src_len);
+        let new_len = self.window_len(self.src_len);
+
+        match diff {
+            SeqDiff::Range(r) => {
+                let (a, b) = (max(r.start, start), min(r.end, start + new_len));
+                if a < b {
+                    self.cast.notify(&SeqDiff::Range(a - start..b - start));
+                }
+            }
+            SeqDiff::Shift { at, by } if *by > 0 => {
+                // items inserted before the window push its first items in
+                let pos = at.saturating_sub(start);
+                let n = min(*by as usize, new_len.saturating_sub(pos));
+                if n > 0 {
+                    self.cast.notify(&SeqDiff::Shift { at: pos, by: n as isize });
+                }
+
+                let overflow = (old_len + n).saturating_sub(new_len);
+                if overflow > 0 {
+                    self.cast.notify(&SeqDiff::Shift { at: new_len, by: -(overflow as isize) });
+                }
+            }
+            SeqDiff::Shift { at, by } => {
+                let pos = min(at.saturating_sub(start), old_len);
+                let n = min(by.unsigned_abs(), old_len - pos);
+                if n > 0 {
+                    self.cast.notify(&SeqDiff::Shift { at: pos, by: -(n as isize) });
+                }
+
+                // the following items move into the window
+                let refill = new_len.saturating_sub(old_len - n);
+                if refill > 0 {
+                    self.cast.notify(&SeqDiff::Shift { at: old_len - n, by: refill as isize });
+                }
+            }
+        }
+    }
+
+    /// items which stay inside the window are kept
+    fn set_range(&mut self, range: Range<usize>) {
+        let old = self.range.start..self.range.start + self.window_len(self.src_len);
+        self.range = range;
+        let new = self.range.start..self.range.start + self.window_len(self.src_len);
+
+        let (a, b) = (max(old.start, new.start), min(old.end, new.end));
+        if a >= b {
+            self.cast.notify_each(SeqDiff::replace(old.len(), new.len()));
+            return;
+        }
+
+        if a > old.start {
+            self.cast.notify(&SeqDiff::Shift { at: 0, by: -((a - old.start) as isize) });
+        }
+        if a > new.start {
+            self.cast.notify(&SeqDiff::Shift { at: 0, by: (a - new.start) as isize });
+        }
+
+        let pos = b - new.start;
+        if old.end > b {
+            self.cast.notify(&SeqDiff::Shift { at: pos, by: -((old.end - b) as isize) });
+        }
+        if new.end > b {
+            self.cast.notify(&SeqDiff::Shift { at: pos, by: (new.end - b) as isize });
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: 'static> View for SliceSequence<Item> {
+    type Msg = SeqDiff;
+}
+
+impl<Item: 'static> SequenceView for SliceSequence<Item> {
+    type Item = Item;
+
+    fn len(&self) -> Option<usize> {
+        Some(self.window_len(self.src_len))
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        if *idx < self.window_len(self.src_len) {
+            self.src.get(&(self.range.start + idx))
+        } else {
+            None
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::{singleton::*, vec::*};
+    use crate::view::{port::UpdateTask, sequence::{SeqDiff, SequenceView, SequenceViewExt}};
+
+    #[test]
+    fn slice_seq1() {
+        let mut buffer = VecBuffer::with_data((0..10).collect::<Vec<i32>>());
+        let mut range = SingletonBuffer::new(2..5);
+
+        let window = buffer.get_port().to_sequence().slice(range.get_port());
+        let window_view = window.get_view().unwrap();
+        window.0.update();
+        assert_eq!(window_view.iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        let notified = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _obs = window.add_notify_fn({
+            let notified = notified.clone();
+            move |diff| notified.lock().unwrap().push(diff.clone())
+        });
+        notified.lock().unwrap().clear();
+
+        // scrolling keeps the overlapping items
+        range.set(3..6);
+        window.0.update();
+        assert_eq!(window_view.iter().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(
+            *notified.lock().unwrap(),
+            vec![SeqDiff::Shift { at: 0, by: -1 }, SeqDiff::Shift { at: 2, by: 1 }]
+        );
+
+        // changes outside of the window are not forwarded
+        notified.lock().unwrap().clear();
+        buffer.update(8, 80);
+        window.0.update();
+        assert!(notified.lock().unwrap().is_empty());
+
+        buffer.insert(0, -1);
+        buffer.update(5, 40);
+        window.0.update();
+        assert_eq!(window_view.iter().collect::<Vec<_>>(), vec![2, 3, 40]);
+
+        buffer.remove(0);
+        buffer.remove(0);
+        window.0.update();
+        assert_eq!(window_view.iter().collect::<Vec<_>>(), vec![40, 5, 6]);
+
+        let tail = buffer.get_port().to_sequence().skip(7);
+        let head = buffer.get_port().to_sequence().take(2);
+        tail.0.update();
+        head.0.update();
+        assert_eq!(tail.get_view().unwrap().iter().collect::<Vec<_>>(), vec![80, 9]);
+        assert_eq!(head.get_view().unwrap().iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        buffer.clear();
+        window.0.update();
+        assert_eq!(window_view.len(), Some(0));
+    }
+}
@@ -0,0 +1,167 @@
+use {
+    crate::{
+        view::{
+            Observer, ObserverBroadcast, OuterViewPort, View, ViewPort,
+            list::{ListView, ListViewExt, ListDiff}
+        },
+        projection::permutation::Permutation,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::cmp::Ordering,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item: Clone + Send + Sync + 'static> OuterViewPort<dyn ListView<Item>> {
+    /// stable sorted list, every source diff moves at most one item
+    pub fn sort_by<F: Fn(&Item, &Item) -> Ordering + Send + Sync + 'static>(
+        &self,
+        cmp: F,
+    ) -> OuterViewPort<dyn ListView<Item>> {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let sort = Arc::new(RwLock::new(SortList {
+            perm: Permutation::new(cmp),
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(sort.clone());
+        port.inner().set_view(Some(sort));
+        port.into_outer()
+    }
+
+    pub fn sort_by_key<K: Ord, F: Fn(&Item) -> K + Send + Sync + 'static>(
+        &self,
+        f: F,
+    ) -> OuterViewPort<dyn ListView<Item>> {
+        self.sort_by(move |a, b| f(a).cmp(&f(b)))
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct SortList<Item, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    F: Fn(&Item, &Item) -> Ordering + Send + Sync,
+{
+    perm: Permutation<Item, F>,
+    cast: Arc<RwLock<ObserverBroadcast<dyn ListView<Item>>>>,
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item, F> View for SortList<Item, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    F: Fn(&Item, &Item) -> Ordering + Send + Sync,
+{
+    type Msg = ListDiff<Item>;
+}
+
+impl<Item, F> ListView<Item> for SortList<Item, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    F: Fn(&Item, &Item) -> Ordering + Send + Sync,
+{
+    fn len(&self) -> Option<usize> {
+        Some(self.perm.len())
+    }
+
+    fn get(&self, idx: &usize) -> Option<Item> {
+        self.perm.get(*idx).cloned()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item, F> Observer<dyn ListView<Item>> for SortList<Item, F>
+where
+    Item: Clone + Send + Sync + 'static,
+    F: Fn(&Item, &Item) -> Ordering + Send + Sync,
+{
+    fn reset(&mut self, view: Option<Arc<dyn ListView<Item>>>) {
+        self.cast.notify(&ListDiff::Clear);
+        match view {
+            Some(v) => self.perm.reset(v.iter()),
+            None => self.perm.clear(),
+        }
+
+        for idx in 0..self.perm.len() {
+            if let Some(val) = self.perm.get(idx) {
+                self.cast.notify(&ListDiff::Insert{ idx, val: val.clone() });
+            }
+        }
+    }
+
+    fn notify(&mut self, msg: &ListDiff<Item>) {
+        match msg {
+            ListDiff::Clear => {
+                self.perm.clear();
+                self.cast.notify(&ListDiff::Clear);
+            }
+            ListDiff::Remove(idx) => {
+                let pos = self.perm.remove(*idx);
+                self.cast.notify(&ListDiff::Remove(pos));
+            }
+            ListDiff::Insert{ idx, val } => {
+                let pos = self.perm.insert(*idx, val.clone());
+                self.cast.notify(&ListDiff::Insert{ idx: pos, val: val.clone() });
+            }
+            ListDiff::Update{ idx, val } => {
+                let (old_pos, new_pos) = self.perm.update(*idx, val.clone());
+                if old_pos == new_pos {
+                    self.cast.notify(&ListDiff::Update{ idx: new_pos, val: val.clone() });
+                } else {
+                    self.cast.notify(&ListDiff::Remove(old_pos));
+                    self.cast.notify(&ListDiff::Insert{ idx: new_pos, val: val.clone() });
+                }
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::{port::UpdateTask, list::ListDiff};
+
+    #[test]
+    fn sort_list1() {
+        let mut buffer = VecBuffer::with_data(vec![3, 1, 2]);
+        let target_port = buffer.get_port().to_list().sort_by_key(|x| *x);
+
+        let mirror = VecBuffer::<i32>::new();
+        let _keepalive = mirror.attach_to(target_port.clone());
+        assert_eq!(*mirror.get_port().get_view().unwrap().read().unwrap(), vec![1, 2, 3]);
+
+        let diffs = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _obs = target_port.add_notify_fn({
+            let diffs = diffs.clone();
+            move |msg| diffs.lock().unwrap().push(match msg {
+                ListDiff::Clear => "clear".to_string(),
+                ListDiff::Remove(idx) => format!("remove {}", idx),
+                ListDiff::Insert{ idx, val } => format!("insert {} {}", idx, val),
+                ListDiff::Update{ idx, val } => format!("update {} {}", idx, val),
+            })
+        });
+
+        buffer.push(0);       // [3, 1, 2, 0]
+        buffer.update(0, 5);  // 3 -> 5, stays last
+        buffer.update(1, 4);  // 1 -> 4, moves
+        buffer.remove(2);     // 2
+
+        target_port.0.update();
+        mirror.get_port().0.update();
+
+        assert_eq!(
+            *diffs.lock().unwrap(),
+            vec!["insert 0 0", "update 3 5", "remove 1", "insert 2 4", "remove 1"]
+        );
+        assert_eq!(*mirror.get_port().get_view().unwrap().read().unwrap(), vec![0, 4, 5]);
+    }
+}
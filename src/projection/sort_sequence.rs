@@ -0,0 +1,199 @@
+use {
+    crate::{
+        view::{
+            Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
+            sequence::{SeqDiff, SeqDiffBatch, SequenceView},
+        },
+        projection::permutation::Permutation,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::cmp::{max, min, Ordering},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<V: SequenceView + ?Sized + 'static> OuterViewPort<V>
+where
+    V::Item: Clone + Send + Sync + 'static,
+{
+    /// stable sorted sequence, every source change only notifies
+    /// the range of positions between the old and new place of the item
+    pub fn sort_by<F: Fn(&V::Item, &V::Item) -> Ordering + Send + Sync + 'static>(
+        &self,
+        cmp: F,
+    ) -> OuterViewPort<dyn SequenceView<Item = V::Item>> {
+        let port = ViewPort::new();
+        port.add_update_hook(Arc::new(self.0.clone()));
+
+        let sort = Arc::new(RwLock::new(SortSequence {
+            src_view: None,
+            perm: Permutation::new(cmp),
+            pending: SeqDiffBatch::default(),
+            cast: port.inner().get_broadcast(),
+        }));
+
+        self.add_observer(sort.clone());
+        port.inner().set_view(Some(sort));
+        port.into_outer()
+    }
+
+    pub fn sort_by_key<K: Ord, F: Fn(&V::Item) -> K + Send + Sync + 'static>(
+        &self,
+        f: F,
+    ) -> OuterViewPort<dyn SequenceView<Item = V::Item>> {
+        self.sort_by(move |a, b| f(a).cmp(&f(b)))
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Keeps a copy of the source items,
+/// since the old value of a changed item is needed to find its position.
+pub struct SortSequence<SrcView, F>
+where
+    SrcView: SequenceView + ?Sized + 'static,
+    SrcView::Item: Clone + Send + Sync,
+    F: Fn(&SrcView::Item, &SrcView::Item) -> Ordering + Send + Sync,
+{
+    src_view: Option<Arc<SrcView>>,
+    perm: Permutation<SrcView::Item, F>,
+
+    /// messages of the running update, the changed items
+    /// are only read once the update is complete
+    pending: SeqDiffBatch,
+    cast: Arc<RwLock<ObserverBroadcast<dyn SequenceView<Item = SrcView::Item>>>>,
+}
+
+impl<SrcView, F> SortSequence<SrcView, F>
+where
+    SrcView: SequenceView + ?Sized + 'static,
+    SrcView::Item: Clone + Send + Sync,
+    F: Fn(&SrcView::Item, &SrcView::Item) -> Ordering + Send + Sync,
+{
+    fn apply(&mut self, diff: &SeqDiff) {
+        match diff {
+            SeqDiff::Range(r) => {
+                for idx in r.clone() {
+                    if let Some(val) = self.src_view.get(&idx) {
+                        let (old_pos, new_pos) = self.perm.update(idx, val);
+                        self.cast.notify(&SeqDiff::Range(min(old_pos, new_pos)..max(old_pos, new_pos) + 1));
+                    }
+                }
+            }
+            SeqDiff::Shift { at, by } if *by > 0 => {
+                for idx in *at..at + *by as usize {
+                    if let Some(val) = self.src_view.get(&idx) {
+                        let pos = self.perm.insert(idx, val);
+                        self.cast.notify(&SeqDiff::Shift { at: pos, by: 1 });
+                    }
+                }
+            }
+            SeqDiff::Shift { at, by } => {
+                for _ in 0..by.unsigned_abs() {
+                    let pos = self.perm.remove(*at);
+                    self.cast.notify(&SeqDiff::Shift { at: pos, by: -1 });
+                }
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<SrcView, F> View for SortSequence<SrcView, F>
+where
+    SrcView: SequenceView + ?Sized + 'static,
+    SrcView::Item: Clone + Send + Sync,
+    F: Fn(&SrcView::Item, &SrcView::Item) -> Ordering + Send + Sync,
+{
+    type Msg = SeqDiff;
+}
+
+impl<SrcView, F> SequenceView for SortSequence<SrcView, F>
+where
+    SrcView: SequenceView + ?Sized + 'static,
+    SrcView::Item: Clone + Send + Sync,
+    F: Fn(&SrcView::Item, &SrcView::Item) -> Ordering + Send + Sync,
+{
+    type Item = SrcView::Item;
+
+    fn len(&self) -> Option<usize> {
+        Some(self.perm.len())
+    }
+
+    fn get(&self, idx: &usize) -> Option<Self::Item> {
+        self.perm.get(*idx).cloned()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<SrcView, F> Observer<SrcView> for SortSequence<SrcView, F>
+where
+    SrcView: SequenceView + ?Sized + 'static,
+    SrcView::Item: Clone + Send + Sync,
+    F: Fn(&SrcView::Item, &SrcView::Item) -> Ordering + Send + Sync,
+{
+    fn reset(&mut self, new_src: Option<Arc<SrcView>>) {
+        let old_len = self.perm.len();
+        self.src_view = new_src;
+
+        let src_len = self.src_view.len().unwrap_or(0);
+        let src_view = self.src_view.clone();
+        self.perm.reset((0..src_len).filter_map(|idx| src_view.get(&idx)));
+
+        self.cast.notify_each(SeqDiff::replace(old_len, self.perm.len()));
+    }
+
+    fn notify(&mut self, diff: &SeqDiff) {
+        self.pending.push(diff.clone());
+    }
+
+    fn end_update(&mut self) {
+        for diff in std::mem::take(&mut self.pending) {
+            self.apply(&diff);
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::{port::UpdateTask, sequence::{SeqDiff, SequenceViewExt}};
+
+    #[test]
+    fn sort_seq1() {
+        let mut buffer = VecBuffer::with_data(vec![30, 10, 20]);
+        let target_port = buffer.get_port().to_sequence().sort_by(|a, b| b.cmp(a));
+        let target_view = target_port.get_view().unwrap();
+
+        let notified = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _obs = target_port.add_notify_fn({
+            let notified = notified.clone();
+            move |diff| notified.lock().unwrap().push(diff.clone())
+        });
+
+        assert_eq!(target_view.iter().collect::<Vec<_>>(), vec![30, 20, 10]);
+
+        buffer.update(1, 25);
+        target_port.0.update();
+        assert_eq!(*notified.lock().unwrap(), vec![SeqDiff::Range(1..3)]);
+        assert_eq!(target_view.iter().collect::<Vec<_>>(), vec![30, 25, 20]);
+
+        notified.lock().unwrap().clear();
+        buffer.update(0, 31);
+        target_port.0.update();
+        assert_eq!(*notified.lock().unwrap(), vec![SeqDiff::Range(0..1)]);
+
+        buffer.push(5);
+        target_port.0.update();
+        assert_eq!(target_view.iter().collect::<Vec<_>>(), vec![31, 25, 20, 5]);
+
+        buffer.clear();
+        target_port.0.update();
+        assert_eq!(target_view.len(), Some(0));
+    }
+}
@@ -1,13 +1,13 @@
 use {
     crate::{
-        view::{
-            Observer, OuterViewPort,
+        view::OuterViewPort,
+        buffer::vec::{VecBuffer, VecDiff},
+        projection::{
+            serialize_diff::{DiffDecodeError, VecEncoder},
+            view2bin::{apply_bin, apply_bin_sync, BinWriter},
         },
-        buffer::{
-            vec::VecDiff,
-        }
     },
-    serde::Serialize,
+    serde::{de::DeserializeOwned, Serialize},
     std::sync::RwLock,
     std::{io::Write, sync::Arc},
 };
@@ -15,14 +15,7 @@ use {
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
 /// Serialization Observer for `Vec`
-pub struct VecBinWriter<T, W>
-where
-    T: Clone + Send + Sync + 'static,
-    W: Write + Send + Sync,
-{
-    data: Option<Arc<RwLock<Vec<T>>>>,
-    out: RwLock<W>,
-}
+pub type VecBinWriter<T, W> = BinWriter<VecEncoder<T>, W>;
 
 impl<T> OuterViewPort<RwLock<Vec<T>>>
 where
@@ -32,54 +25,74 @@ where
         &self,
         out: W,
     ) -> Arc<RwLock<VecBinWriter<T, W>>> {
-        let writer = Arc::new(RwLock::new(VecBinWriter {
-            data: None,
-            out: RwLock::new(out),
-        }));
-        self.add_observer(writer.clone());
-        writer
+        self.serialize_bin_with(VecEncoder::default(), out, None)
     }
 }
 
-impl<T, W> Observer<RwLock<Vec<T>>> for VecBinWriter<T, W>
+impl<T> VecBuffer<T>
 where
-    T: Clone + Serialize + Send + Sync + 'static,
-    W: Write + Send + Sync,
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
 {
-    fn reset(&mut self, view: Option<Arc<RwLock<Vec<T>>>>) {
-        self.data = view;
-        let mut out = self.out.write().unwrap();
+    /// apply all diffs written by a `VecBinWriter` until the end of the input.
+    /// All complete frames before a truncated one are applied.
+    pub async fn from_bin<R: async_std::io::Read + Unpin>(
+        &mut self,
+        read: R,
+    ) -> Result<(), DiffDecodeError> {
+        apply_bin::<VecDiff<T>, _, _>(self, read).await
+    }
 
-        out.write(
-            &bincode::serialized_size(&VecDiff::<T>::Clear)
-                .unwrap()
-                .to_le_bytes(),
-        )
-        .expect("");
-        out.write(&bincode::serialize(&VecDiff::<T>::Clear).unwrap())
-            .expect("");
+    /// blocking variant of `from_bin()`
+    pub fn from_bin_sync<R: std::io::Read>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_bin_sync::<VecDiff<T>, _, _>(self, read)
+    }
+}
 
-        if let Some(data) = self.data.as_ref() {
-            for x in data.read().unwrap().iter() {
-                out.write(
-                    &bincode::serialized_size(&VecDiff::Push(x))
-                        .unwrap()
-                        .to_le_bytes(),
-                )
-                .expect("");
-                out.write(&bincode::serialize(&VecDiff::Push(x)).unwrap())
-                    .expect("");
-            }
-        }
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::vec::*,
+        projection::serialize_diff::DiffDecodeError,
+        testing::shared_buf::SharedBuf,
+        view::port::UpdateTask,
+    };
+
+    #[async_std::test]
+    async fn vec_bin_roundtrip() {
+        let mut src = VecBuffer::<String>::with_data(vec!["a".into()]);
+        let out = SharedBuf::default();
+        let _writer = src.get_port().serialize_bin(out.clone());
+
+        src.push("b".into());
+        src.insert(0, "c".into());
+        src.remove(1);
+        src.get_port().0.update();
 
-        out.flush().expect("");
+        let mut dst = VecBuffer::<String>::new();
+        dst.from_bin(&out.take()[..]).await.unwrap();
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec!["c", "b"]);
     }
 
-    fn notify(&mut self, diff: &VecDiff<T>) {
-        let mut out = self.out.write().unwrap();
-        out.write(&bincode::serialized_size(diff).unwrap().to_le_bytes())
-            .expect("");
-        out.write(&bincode::serialize(diff).unwrap()).expect("");
-        out.flush().expect("");
+    #[test]
+    fn vec_bin_truncated() {
+        let src = VecBuffer::<u32>::with_data(vec![1, 2, 3]);
+        let out = SharedBuf::default();
+        let _writer = src.get_port().serialize_bin(out.clone());
+
+        let bytes = out.take();
+        for cut in 1..bytes.len() {
+            let mut dst = VecBuffer::<u32>::new();
+            match dst.from_bin_sync(&bytes[..cut]) {
+                Ok(()) | Err(DiffDecodeError::Truncated { .. }) => {}
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+            assert!(dst.len() < 3);
+        }
+
+        let mut dst = VecBuffer::<u32>::new();
+        dst.from_bin_sync(&bytes[..]).unwrap();
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec![1, 2, 3]);
     }
 }
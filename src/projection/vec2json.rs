@@ -1,12 +1,13 @@
 use {
     crate::{
-        view::{Observer, OuterViewPort},
+        view::OuterViewPort,
         buffer::vec::{VecBuffer, VecDiff},
+        projection::{
+            serialize_diff::{DiffDecodeError, VecEncoder},
+            view2json::{apply_json, JsonWriter},
+        },
     },
-    async_std::{
-        io::{Read, ReadExt},
-        stream::StreamExt,
-    },
+    async_std::io::Read,
     serde::{de::DeserializeOwned, Serialize},
     std::sync::RwLock,
     std::{io::Write, sync::Arc},
@@ -14,14 +15,8 @@ use {
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
-pub struct VecJsonWriter<T, W>
-where
-    T: Clone + Send + Sync + 'static,
-    W: Write + Send + Sync,
-{
-    data: Option<Arc<RwLock<Vec<T>>>>,
-    out: RwLock<W>,
-}
+/// Serialization Observer for `Vec`
+pub type VecJsonWriter<T, W> = JsonWriter<VecEncoder<T>, W>;
 
 impl<T> OuterViewPort<RwLock<Vec<T>>>
 where
@@ -31,80 +26,84 @@ where
         &self,
         out: W,
     ) -> Arc<RwLock<VecJsonWriter<T, W>>> {
-        let writer = Arc::new(RwLock::new(VecJsonWriter {
-            data: None,
-            out: RwLock::new(out),
-        }));
-        self.add_observer(writer.clone());
-        writer
+        self.serialize_json_with(VecEncoder::default(), out, None)
     }
 }
 
-impl<T, W> Observer<RwLock<Vec<T>>> for VecJsonWriter<T, W>
+impl<T> VecBuffer<T>
 where
-    T: Clone + Serialize + Send + Sync + 'static,
-    W: Write + Send + Sync,
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
 {
-    fn reset(&mut self, view: Option<Arc<RwLock<Vec<T>>>>) {
-        self.data = view;
+    pub async fn from_json<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_json::<VecDiff<T>, _, _>(self, read).await
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
-        self.out
-            .write()
-            .unwrap()
-            .write(
-                &serde_json::to_string(&VecDiff::<T>::Clear)
-                    .unwrap()
-                    .as_bytes(),
-            )
-            .expect("");
-        self.out.write().unwrap().write(b"\n").expect("");
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            buffer::vec::*,
+            projection::serialize_diff::{DiffDecodeError, SerializeError, VecEncoder},
+            testing::shared_buf::SharedBuf,
+            view::port::UpdateTask,
+        },
+        std::sync::{atomic::{AtomicUsize, Ordering}, Arc},
+    };
 
-        if let Some(data) = self.data.as_ref() {
-            for x in data.read().unwrap().iter() {
-                self.out
-                    .write()
-                    .unwrap()
-                    .write(&serde_json::to_string(&VecDiff::Push(x)).unwrap().as_bytes())
-                    .expect("");
-                self.out.write().unwrap().write(b"\n").expect("");
-            }
-        }
+    #[async_std::test]
+    async fn vec_json_decode_error() {
+        let mut dst = VecBuffer::<u32>::new();
+        let input = b"\"Clear\"\n{\"Push\":1}\n{\"Push\":\n{\"Push\":2}\n";
 
-        self.out.write().unwrap().flush().expect("");
+        match dst.from_json(&input[..]).await {
+            Err(DiffDecodeError::Json { line, .. }) => assert_eq!(line, 3),
+            _ => panic!("expected json error"),
+        }
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec![1]);
     }
 
-    fn notify(&mut self, diff: &VecDiff<T>) {
-        self.out
-            .write()
-            .unwrap()
-            .write(serde_json::to_string(diff).unwrap().as_bytes())
-            .expect("");
-        self.out.write().unwrap().write(b"\n").expect("");
-        self.out.write().unwrap().flush().expect("");
+    struct BrokenPipe;
+
+    impl std::io::Write for BrokenPipe {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
     }
-}
 
-impl<T> VecBuffer<T>
-where
-    T: DeserializeOwned + Clone + Send + Sync + 'static,
-{
-    pub async fn from_json<R: Read + async_std::io::Read + Unpin>(&mut self, read: R) {
-        let mut bytes = read.bytes();
-        let mut s = String::new();
-        while let Some(Ok(b)) = bytes.next().await {
-            match b {
-                b'\n' => {
-                    if s.len() > 0 {
-                        let diff =
-                            serde_json::from_str::<VecDiff<T>>(&s).expect("error parsing json");
-                        self.apply_diff(diff);
-                        s.clear();
-                    }
-                }
-                c => {
-                    s.push(c as char);
+    #[test]
+    fn vec_json_write_error() {
+        let mut src = VecBuffer::<u32>::with_data(vec![1]);
+        let out = SharedBuf::default();
+        let writer = src.get_port().serialize_json(out.clone());
+        assert!(writer.read().unwrap().error().is_none());
+        assert!(!out.take().is_empty());
+
+        // the initial snapshot already fails
+        let errors = Arc::new(AtomicUsize::new(0));
+        let writer = src.get_port().serialize_json_with(
+            VecEncoder::default(),
+            BrokenPipe,
+            Some(Box::new({
+                let errors = errors.clone();
+                move |err| {
+                    assert!(matches!(err, SerializeError::Io(e) if e.kind() == std::io::ErrorKind::BrokenPipe));
+                    errors.fetch_add(1, Ordering::SeqCst);
                 }
-            }
-        }
+            })),
+        );
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+        assert!(writer.read().unwrap().error().is_some());
+
+        // the writer stops after the first error
+        src.push(2);
+        src.get_port().0.update();
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
     }
 }
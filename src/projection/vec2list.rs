@@ -68,6 +68,7 @@ where
         match diff {
             VecDiff::Clear => {
                 self.cast.notify(&ListDiff::Clear);
+                self.cur_len = 0;
             }
             VecDiff::Push(val) => {
                 self.cast.notify(&ListDiff::Insert{
@@ -2,7 +2,7 @@ use {
     crate::{
         view::{
             InnerViewPort, Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
-            sequence::SequenceView,
+            sequence::{SeqDiff, SequenceView},
         },
         buffer::vec::VecDiff,
     },
@@ -51,29 +51,29 @@ where
         };
 
         self.cur_len = new_len;
-        self.cast.notify_each(0..std::cmp::max(old_len, new_len));
+        self.cast.notify_each(SeqDiff::replace(old_len, new_len));
     }
 
     fn notify(&mut self, diff: &VecDiff<T>) {
         match diff {
             VecDiff::Clear => {
-                self.cast.notify_each(0..self.cur_len);
+                self.cast.notify_each(SeqDiff::replace(self.cur_len, 0));
                 self.cur_len = 0
             }
             VecDiff::Push(_) => {
-                self.cast.notify(&self.cur_len);
+                self.cast.notify(&SeqDiff::Shift { at: self.cur_len, by: 1 });
                 self.cur_len += 1;
             }
             VecDiff::Remove(idx) => {
-                self.cast.notify_each(*idx..self.cur_len);
+                self.cast.notify(&SeqDiff::Shift { at: *idx, by: -1 });
                 self.cur_len -= 1;
             }
             VecDiff::Insert { idx, val: _ } => {
                 self.cur_len += 1;
-                self.cast.notify_each(*idx..self.cur_len);
+                self.cast.notify(&SeqDiff::Shift { at: *idx, by: 1 });
             }
             VecDiff::Update { idx, val: _ } => {
-                self.cast.notify(&idx);
+                self.cast.notify(&SeqDiff::Range(*idx..*idx + 1));
             }
         }
     }
@@ -83,7 +83,7 @@ impl<T> View for VecSequence<T>
 where
     T: Clone + Send + Sync + 'static,
 {
-    type Msg = usize;
+    type Msg = SeqDiff;
 }
 
 impl<T> SequenceView for VecSequence<T>
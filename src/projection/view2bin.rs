@@ -0,0 +1,331 @@
+use {
+    crate::{
+        buffer::{index_hashmap::IndexBuffer, singleton::SingletonBuffer, vec::VecBuffer},
+        projection::serialize_diff::*,
+        view::{
+            index::IndexView,
+            list::{ListDiff, ListView},
+            sequence::SequenceView,
+            singleton::SingletonView,
+            Observer, OuterViewPort, View,
+        },
+    },
+    async_std::io::{Read, ReadExt},
+    serde::{de::DeserializeOwned, Serialize},
+    std::sync::RwLock,
+    std::{hash::Hash, io::Write, sync::Arc},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Serialization Observer which writes every diff
+/// bincode-encoded and prefixed by its length as little-endian `u64`
+pub struct BinWriter<E, W>
+where
+    E: DiffEncoder,
+    W: Write + Send + Sync,
+{
+    encoder: E,
+    out: RwLock<W>,
+    error: WriteError,
+}
+
+impl<E, W> BinWriter<E, W>
+where
+    E: DiffEncoder,
+    E::Diff: Serialize,
+    W: Write + Send + Sync,
+{
+    /// the error which stopped this writer
+    pub fn error(&self) -> Option<&SerializeError> {
+        self.error.get()
+    }
+
+    fn try_write_diffs(&mut self, diffs: Vec<E::Diff>) -> Result<(), SerializeError> {
+        write_frames(&mut *self.out.write().unwrap(), diffs)
+    }
+
+    fn write_diffs(&mut self, diffs: Result<Vec<E::Diff>, SerializeError>) {
+        if !self.error.is_set() {
+            let result = diffs.and_then(|diffs| self.try_write_diffs(diffs));
+            self.error.handle(result);
+        }
+    }
+}
+
+impl<E, W> Observer<E::View> for BinWriter<E, W>
+where
+    E: DiffEncoder,
+    E::Diff: Serialize,
+    W: Write + Send + Sync,
+{
+    fn reset(&mut self, view: Option<Arc<E::View>>) {
+        let diffs = self.encoder.reset(view);
+        self.write_diffs(diffs);
+    }
+
+    fn notify(&mut self, msg: &<E::View as View>::Msg) {
+        let diffs = self.encoder.encode(msg);
+        self.write_diffs(diffs);
+    }
+}
+
+impl<V: View + ?Sized + 'static> OuterViewPort<V>
+where
+    V::Msg: Clone,
+{
+    /// `on_error` is invoked on the first error, including one in the initial snapshot
+    pub fn serialize_bin_with<E, W>(&self, encoder: E, out: W, on_error: Option<ErrorCallback>) -> Arc<RwLock<BinWriter<E, W>>>
+    where
+        E: DiffEncoder<View = V> + 'static,
+        E::Diff: Serialize,
+        W: Write + Send + Sync + 'static,
+    {
+        let writer = Arc::new(RwLock::new(BinWriter {
+            encoder,
+            out: RwLock::new(out),
+            error: WriteError::new(on_error),
+        }));
+        self.add_observer(writer.clone());
+        writer
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<T> OuterViewPort<dyn SingletonView<Item = T>>
+where
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn serialize_bin<W: Write + Send + Sync + 'static>(
+        &self,
+        out: W,
+    ) -> Arc<RwLock<BinWriter<SingletonEncoder<T>, W>>> {
+        self.serialize_bin_with(SingletonEncoder::default(), out, None)
+    }
+}
+
+impl<T> OuterViewPort<dyn ListView<T>>
+where
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn serialize_bin<W: Write + Send + Sync + 'static>(
+        &self,
+        out: W,
+    ) -> Arc<RwLock<BinWriter<ListEncoder<T>, W>>> {
+        self.serialize_bin_with(ListEncoder::default(), out, None)
+    }
+}
+
+impl<T> OuterViewPort<dyn SequenceView<Item = T>>
+where
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn serialize_bin<W: Write + Send + Sync + 'static>(
+        &self,
+        out: W,
+    ) -> Arc<RwLock<BinWriter<SequenceEncoder<T>, W>>> {
+        self.serialize_bin_with(SequenceEncoder::default(), out, None)
+    }
+}
+
+impl<Key, T> OuterViewPort<dyn IndexView<Key, Item = T>>
+where
+    Key: Clone + Hash + Eq + Serialize + Send + Sync + 'static,
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn serialize_bin<W: Write + Send + Sync + 'static>(
+        &self,
+        out: W,
+    ) -> Arc<RwLock<BinWriter<IndexEncoder<Key, T>, W>>> {
+        self.serialize_bin_with(IndexEncoder::default(), out, None)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// write every diff as length-prefixed bincode frame
+pub fn write_frames<D, W>(out: &mut W, diffs: Vec<D>) -> Result<(), SerializeError>
+where
+    D: Serialize,
+    W: Write + ?Sized,
+{
+    for diff in diffs {
+        out.write_all(&bincode::serialized_size(&diff)?.to_le_bytes())?;
+        out.write_all(&bincode::serialize(&diff)?)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// read length-prefixed bincode diffs and apply them to `target`
+/// until the end of the input
+pub async fn apply_bin<D, B, R>(target: &mut B, mut read: R) -> Result<(), DiffDecodeError>
+where
+    D: DeserializeOwned,
+    B: ApplyDiff<D>,
+    R: Read + Unpin,
+{
+    let mut frame = 1;
+    loop {
+        let mut len_bytes = Vec::new();
+        (&mut read)
+            .take(8)
+            .read_to_end(&mut len_bytes)
+            .await
+            .map_err(|error| DiffDecodeError::Io { frame, error })?;
+
+        let len = match frame_len(&len_bytes, frame)? {
+            Some(len) => len,
+            None => return Ok(()),
+        };
+
+        let mut buf = Vec::new();
+        (&mut read)
+            .take(len)
+            .read_to_end(&mut buf)
+            .await
+            .map_err(|error| DiffDecodeError::Io { frame, error })?;
+
+        target.apply(decode_frame(&buf, len, frame)?);
+        frame += 1;
+    }
+}
+
+/// blocking variant of `apply_bin()`
+pub fn apply_bin_sync<D, B, R>(target: &mut B, mut read: R) -> Result<(), DiffDecodeError>
+where
+    D: DeserializeOwned,
+    B: ApplyDiff<D>,
+    R: std::io::Read,
+{
+    let mut frame = 1;
+    while let Some(diff) = read_frame_sync::<D, _>(&mut read, frame)? {
+        target.apply(diff);
+        frame += 1;
+    }
+    Ok(())
+}
+
+/// read a single length-prefixed diff, `None` at the end of the input
+pub fn read_frame_sync<D, R>(read: &mut R, frame: usize) -> Result<Option<D>, DiffDecodeError>
+where
+    D: DeserializeOwned,
+    R: std::io::Read,
+{
+    let mut len_bytes = Vec::new();
+    std::io::Read::read_to_end(&mut std::io::Read::take(&mut *read, 8), &mut len_bytes)
+        .map_err(|error| DiffDecodeError::Io { frame, error })?;
+
+    let len = match frame_len(&len_bytes, frame)? {
+        Some(len) => len,
+        None => return Ok(None),
+    };
+
+    let mut buf = Vec::new();
+    std::io::Read::read_to_end(&mut std::io::Read::take(&mut *read, len), &mut buf)
+        .map_err(|error| DiffDecodeError::Io { frame, error })?;
+
+    decode_frame(&buf, len, frame).map(Some)
+}
+
+/// decode the length prefix of a frame, `None` at the end of the input
+fn frame_len(len_bytes: &[u8], frame: usize) -> Result<Option<u64>, DiffDecodeError> {
+    match len_bytes.len() {
+        0 => Ok(None),
+        8 => {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(len_bytes);
+            Ok(Some(u64::from_le_bytes(buf)))
+        }
+        _ => Err(DiffDecodeError::Truncated { frame }),
+    }
+}
+
+fn decode_frame<D>(buf: &[u8], len: u64, frame: usize) -> Result<D, DiffDecodeError>
+where
+    D: DeserializeOwned,
+{
+    if (buf.len() as u64) < len {
+        return Err(DiffDecodeError::Truncated { frame });
+    }
+
+    bincode::deserialize::<D>(buf).map_err(|error| DiffDecodeError::Bincode { frame, error })
+}
+
+impl<T> SingletonBuffer<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    pub async fn from_bin<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_bin::<T, _, _>(self, read).await
+    }
+}
+
+impl<T> VecBuffer<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// replicate a `ListView` serialized with `serialize_bin()`
+    pub async fn from_list_bin<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_bin::<ListDiff<T>, _, _>(self, read).await
+    }
+
+    /// replicate a `SequenceView` serialized with `serialize_bin()`
+    pub async fn from_sequence_bin<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_bin::<SequenceUpdate<T>, _, _>(self, read).await
+    }
+}
+
+impl<Key, T> IndexBuffer<Key, T>
+where
+    Key: DeserializeOwned + Clone + Hash + Eq + Send + Sync + 'static,
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    pub async fn from_bin<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_bin::<IndexUpdate<Key, T>, _, _>(self, read).await
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::{index_hashmap::*, vec::*},
+        testing::shared_buf::SharedBuf,
+        view::port::UpdateTask,
+    };
+
+    #[async_std::test]
+    async fn sequence_bin() {
+        let mut src = VecBuffer::<i32>::with_data(vec![1, 2]);
+        let port = src.get_port().to_sequence();
+        let out = SharedBuf::default();
+        let _writer = port.serialize_bin(out.clone());
+
+        let mut dst = VecBuffer::<i32>::new();
+        dst.from_sequence_bin(&out.take()[..]).await.unwrap();
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec![1, 2]);
+
+        src.insert(0, 0);
+        port.0.update();
+        dst.from_sequence_bin(&out.take()[..]).await.unwrap();
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec![0, 1, 2]);
+    }
+
+    #[async_std::test]
+    async fn index_bin() {
+        let mut src = IndexBuffer::<u32, String>::new();
+        let port = src.get_port();
+        let out = SharedBuf::default();
+        let _writer = port.serialize_bin(out.clone());
+
+        src.insert(3, "x".into());
+        port.0.update();
+
+        let mut dst = IndexBuffer::<u32, _>::new();
+        dst.from_bin(&out.take()[..]).await.unwrap();
+        assert_eq!(dst.get(&3), Some("x".to_string()));
+    }
+}
@@ -0,0 +1,322 @@
+use {
+    crate::{
+        buffer::{index_hashmap::IndexBuffer, singleton::SingletonBuffer, vec::VecBuffer},
+        projection::serialize_diff::*,
+        view::{
+            index::IndexView,
+            list::{ListDiff, ListView},
+            sequence::SequenceView,
+            singleton::SingletonView,
+            Observer, OuterViewPort, View,
+        },
+    },
+    async_std::{
+        io::{Read, ReadExt},
+        stream::StreamExt,
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    std::sync::RwLock,
+    std::{hash::Hash, io::Write, sync::Arc},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Serialization Observer which writes one JSON-encoded diff per line
+pub struct JsonWriter<E, W>
+where
+    E: DiffEncoder,
+    W: Write + Send + Sync,
+{
+    encoder: E,
+    out: RwLock<W>,
+    error: WriteError,
+}
+
+impl<E, W> JsonWriter<E, W>
+where
+    E: DiffEncoder,
+    E::Diff: Serialize,
+    W: Write + Send + Sync,
+{
+    /// the error which stopped this writer
+    pub fn error(&self) -> Option<&SerializeError> {
+        self.error.get()
+    }
+
+    fn try_write_diffs(&mut self, diffs: Vec<E::Diff>) -> Result<(), SerializeError> {
+        let mut out = self.out.write().unwrap();
+        for diff in diffs {
+            out.write_all(serde_json::to_string(&diff)?.as_bytes())?;
+            out.write_all(b"\n")?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    fn write_diffs(&mut self, diffs: Result<Vec<E::Diff>, SerializeError>) {
+        if !self.error.is_set() {
+            let result = diffs.and_then(|diffs| self.try_write_diffs(diffs));
+            self.error.handle(result);
+        }
+    }
+}
+
+impl<E, W> Observer<E::View> for JsonWriter<E, W>
+where
+    E: DiffEncoder,
+    E::Diff: Serialize,
+    W: Write + Send + Sync,
+{
+    fn reset(&mut self, view: Option<Arc<E::View>>) {
+        let diffs = self.encoder.reset(view);
+        self.write_diffs(diffs);
+    }
+
+    fn notify(&mut self, msg: &<E::View as View>::Msg) {
+        let diffs = self.encoder.encode(msg);
+        self.write_diffs(diffs);
+    }
+}
+
+impl<V: View + ?Sized + 'static> OuterViewPort<V>
+where
+    V::Msg: Clone,
+{
+    /// `on_error` is invoked on the first error, including one in the initial snapshot
+    pub fn serialize_json_with<E, W>(&self, encoder: E, out: W, on_error: Option<ErrorCallback>) -> Arc<RwLock<JsonWriter<E, W>>>
+    where
+        E: DiffEncoder<View = V> + 'static,
+        E::Diff: Serialize,
+        W: Write + Send + Sync + 'static,
+    {
+        let writer = Arc::new(RwLock::new(JsonWriter {
+            encoder,
+            out: RwLock::new(out),
+            error: WriteError::new(on_error),
+        }));
+        self.add_observer(writer.clone());
+        writer
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<T> OuterViewPort<dyn SingletonView<Item = T>>
+where
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn serialize_json<W: Write + Send + Sync + 'static>(
+        &self,
+        out: W,
+    ) -> Arc<RwLock<JsonWriter<SingletonEncoder<T>, W>>> {
+        self.serialize_json_with(SingletonEncoder::default(), out, None)
+    }
+}
+
+impl<T> OuterViewPort<dyn ListView<T>>
+where
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn serialize_json<W: Write + Send + Sync + 'static>(
+        &self,
+        out: W,
+    ) -> Arc<RwLock<JsonWriter<ListEncoder<T>, W>>> {
+        self.serialize_json_with(ListEncoder::default(), out, None)
+    }
+}
+
+impl<T> OuterViewPort<dyn SequenceView<Item = T>>
+where
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn serialize_json<W: Write + Send + Sync + 'static>(
+        &self,
+        out: W,
+    ) -> Arc<RwLock<JsonWriter<SequenceEncoder<T>, W>>> {
+        self.serialize_json_with(SequenceEncoder::default(), out, None)
+    }
+}
+
+impl<Key, T> OuterViewPort<dyn IndexView<Key, Item = T>>
+where
+    Key: Clone + Hash + Eq + Serialize + Send + Sync + 'static,
+    T: Clone + Serialize + Send + Sync + 'static,
+{
+    pub fn serialize_json<W: Write + Send + Sync + 'static>(
+        &self,
+        out: W,
+    ) -> Arc<RwLock<JsonWriter<IndexEncoder<Key, T>, W>>> {
+        self.serialize_json_with(IndexEncoder::default(), out, None)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// read JSON-encoded diffs line by line and apply them to `target`
+pub async fn apply_json<D, B, R>(target: &mut B, read: R) -> Result<(), DiffDecodeError>
+where
+    D: DeserializeOwned,
+    B: ApplyDiff<D>,
+    R: Read + Unpin,
+{
+    let mut bytes = read.bytes();
+    let mut line = Vec::new();
+    let mut line_nr = 1;
+    loop {
+        match bytes.next().await {
+            Some(Ok(b'\n')) => {
+                apply_json_line(target, &line, line_nr)?;
+                line.clear();
+                line_nr += 1;
+            }
+            Some(Ok(c)) => {
+                line.push(c);
+            }
+            Some(Err(error)) => {
+                return Err(DiffDecodeError::Io { frame: line_nr, error });
+            }
+            None => {
+                return apply_json_line(target, &line, line_nr);
+            }
+        }
+    }
+}
+
+fn apply_json_line<D, B>(target: &mut B, line: &[u8], line_nr: usize) -> Result<(), DiffDecodeError>
+where
+    D: DeserializeOwned,
+    B: ApplyDiff<D>,
+{
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return Ok(());
+    }
+
+    let diff = serde_json::from_slice::<D>(line)
+        .map_err(|error| DiffDecodeError::Json { line: line_nr, error })?;
+    target.apply(diff);
+    Ok(())
+}
+
+impl<T> SingletonBuffer<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    pub async fn from_json<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_json::<T, _, _>(self, read).await
+    }
+}
+
+impl<T> VecBuffer<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// replicate a `ListView` serialized with `serialize_json()`
+    pub async fn from_list_json<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_json::<ListDiff<T>, _, _>(self, read).await
+    }
+
+    /// replicate a `SequenceView` serialized with `serialize_json()`
+    pub async fn from_sequence_json<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_json::<SequenceUpdate<T>, _, _>(self, read).await
+    }
+}
+
+impl<Key, T> IndexBuffer<Key, T>
+where
+    Key: DeserializeOwned + Clone + Hash + Eq + Send + Sync + 'static,
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    pub async fn from_json<R: Read + Unpin>(&mut self, read: R) -> Result<(), DiffDecodeError> {
+        apply_json::<IndexUpdate<Key, T>, _, _>(self, read).await
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::{index_hashmap::*, singleton::*, vec::*},
+        testing::shared_buf::SharedBuf,
+        view::port::UpdateTask,
+    };
+
+    #[async_std::test]
+    async fn list_json() {
+        let mut src = VecBuffer::<char>::with_data(vec!['a', 'b']);
+        let port = src.get_port().to_list();
+        let out = SharedBuf::default();
+        let _writer = port.serialize_json(out.clone());
+
+        let mut dst = VecBuffer::<char>::new();
+        dst.from_list_json(&out.take()[..]).await.unwrap();
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec!['a', 'b']);
+
+        src.remove(0);
+        src.insert(1, 'c');
+        port.0.update();
+        dst.from_list_json(&out.take()[..]).await.unwrap();
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec!['b', 'c']);
+    }
+
+    #[async_std::test]
+    async fn sequence_json() {
+        let mut src = VecBuffer::<i32>::with_data(vec![1, 2, 3]);
+        let port = src.get_port().to_sequence().map(|x| x * 10);
+        let out = SharedBuf::default();
+        let _writer = port.serialize_json(out.clone());
+
+        let mut dst = VecBuffer::<i32>::new();
+        dst.from_sequence_json(&out.take()[..]).await.unwrap();
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec![10, 20, 30]);
+
+        src.remove(0);
+        src.push(4);
+        src.push(5);
+        port.0.update();
+        dst.from_sequence_json(&out.take()[..]).await.unwrap();
+        assert_eq!(*dst.get_port().get_view().unwrap().read().unwrap(), vec![20, 30, 40, 50]);
+
+        src.clear();
+        port.0.update();
+        dst.from_sequence_json(&out.take()[..]).await.unwrap();
+        assert_eq!(dst.len(), 0);
+    }
+
+    #[async_std::test]
+    async fn singleton_json() {
+        let mut src = SingletonBuffer::new(1);
+        let port = src.get_port().map(|x| x + 1);
+        let out = SharedBuf::default();
+        let _writer = port.serialize_json(out.clone());
+
+        let mut dst = SingletonBuffer::new(0);
+        dst.from_json(&out.take()[..]).await.unwrap();
+        assert_eq!(dst.get(), 2);
+
+        src.set(5);
+        port.0.update();
+        dst.from_json(&out.take()[..]).await.unwrap();
+        assert_eq!(dst.get(), 6);
+    }
+
+    #[async_std::test]
+    async fn index_json() {
+        let mut src = IndexBuffer::<u32, char>::new();
+        src.insert(1, 'a');
+        let port = src.get_port();
+        let out = SharedBuf::default();
+        let _writer = port.serialize_json(out.clone());
+
+        let mut dst = IndexBuffer::<u32, _>::new();
+        dst.from_json(&out.take()[..]).await.unwrap();
+        assert_eq!(dst.get(&1), Some('a'));
+
+        src.insert(2, 'b');
+        src.remove(1);
+        port.0.update();
+        dst.from_json(&out.take()[..]).await.unwrap();
+        assert_eq!(dst.get(&1), None);
+        assert_eq!(dst.get(&2), Some('b'));
+    }
+}
@@ -0,0 +1,194 @@
+use {
+    crate::{
+        view::{
+            InnerViewPort, Observer, ObserverBroadcast, ObserverExt, OuterViewPort, View, ViewPort,
+            list::{ListView, ListDiff}
+        },
+        projection::projection_helper::ProjectionHelper,
+    },
+    std::sync::Arc,
+    std::sync::RwLock,
+    std::cmp::min,
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<A: Clone + Send + Sync + 'static> OuterViewPort<dyn ListView<A>> {
+    /// pairs of items at the same position,
+    /// as long as the shorter of both lists
+    pub fn zip<B: Clone + Send + Sync + 'static>(
+        &self,
+        other: &OuterViewPort<dyn ListView<B>>,
+    ) -> OuterViewPort<dyn ListView<(A, B)>> {
+        let port = ViewPort::new();
+        ZipList::new(self.clone(), other.clone(), port.inner());
+        port.into_outer()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+type PairCast<A, B> = Arc<RwLock<ObserverBroadcast<dyn ListView<(A, B)>>>>;
+
+/// Keeps a copy of both lists, since inserting into only one of them
+/// changes all following pairs.
+pub struct ZipList<A, B>
+where
+    A: Clone + Send + Sync + 'static,
+    B: Clone + Send + Sync + 'static,
+{
+    a: Vec<A>,
+    b: Vec<B>,
+    cast: PairCast<A, B>,
+    proj_helper: ProjectionHelper<usize, Self>,
+}
+
+impl<A, B> ZipList<A, B>
+where
+    A: Clone + Send + Sync + 'static,
+    B: Clone + Send + Sync + 'static,
+{
+    pub fn new(
+        a_port: OuterViewPort<dyn ListView<A>>,
+        b_port: OuterViewPort<dyn ListView<B>>,
+        out_port: InnerViewPort<dyn ListView<(A, B)>>,
+    ) -> Arc<RwLock<Self>> {
+        let mut proj_helper = ProjectionHelper::new(out_port.0.update_hooks.clone());
+
+        proj_helper.new_list_arg(0, a_port, |s: &mut Self, diff| {
+            let old_len = s.cur_len();
+            let start = apply(&mut s.a, diff);
+            s.notify_from(diff, start, old_len);
+        });
+        proj_helper.new_list_arg(1, b_port, |s: &mut Self, diff| {
+            let old_len = s.cur_len();
+            let start = apply(&mut s.b, diff);
+            s.notify_from(diff, start, old_len);
+        });
+
+        let zip = Arc::new(RwLock::new(ZipList {
+            a: Vec::new(),
+            b: Vec::new(),
+            cast: out_port.get_broadcast(),
+            proj_helper,
+        }));
+
+        zip.write().unwrap().proj_helper.set_proj(&zip);
+        out_port.set_view(Some(zip.clone()));
+        zip
+    }
+
+    fn cur_len(&self) -> usize {
+        min(self.a.len(), self.b.len())
+    }
+
+    fn pair(&self, idx: usize) -> (A, B) {
+        (self.a[idx].clone(), self.b[idx].clone())
+    }
+
+    /// one of the lists changed at `start`, every pair before it stays the same
+    fn notify_from(&mut self, diff: &ListDiff<impl Clone + Send + Sync + 'static>, start: usize, old_len: usize) {
+        let new_len = self.cur_len();
+
+        match diff {
+            ListDiff::Clear => {
+                if old_len > 0 {
+                    self.cast.notify(&ListDiff::Clear);
+                }
+            }
+            ListDiff::Update{ idx, .. } => {
+                if *idx < new_len {
+                    self.cast.notify(&ListDiff::Update{ idx: *idx, val: self.pair(*idx) });
+                }
+            }
+            ListDiff::Insert{ .. } | ListDiff::Remove(_) => {
+                for idx in start..min(old_len, new_len) {
+                    self.cast.notify(&ListDiff::Update{ idx, val: self.pair(idx) });
+                }
+                for idx in old_len..new_len {
+                    self.cast.notify(&ListDiff::Insert{ idx, val: self.pair(idx) });
+                }
+                self.cast.notify_each((new_len..old_len).rev().map(ListDiff::Remove));
+            }
+        }
+    }
+}
+
+/// apply `diff` to `items`, returns the first changed position
+fn apply<T: Clone + Send + Sync + 'static>(items: &mut Vec<T>, diff: &ListDiff<T>) -> usize {
+    match diff {
+        ListDiff::Clear => {
+            items.clear();
+            0
+        }
+        ListDiff::Remove(idx) => {
+            items.remove(*idx);
+            *idx
+        }
+        ListDiff::Insert{ idx, val } => {
+            items.insert(*idx, val.clone());
+            *idx
+        }
+        ListDiff::Update{ idx, val } => {
+            items[*idx] = val.clone();
+            *idx
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<A, B> View for ZipList<A, B>
+where
+    A: Clone + Send + Sync + 'static,
+    B: Clone + Send + Sync + 'static,
+{
+    type Msg = ListDiff<(A, B)>;
+}
+
+impl<A, B> ListView<(A, B)> for ZipList<A, B>
+where
+    A: Clone + Send + Sync + 'static,
+    B: Clone + Send + Sync + 'static,
+{
+    fn len(&self) -> Option<usize> {
+        Some(self.cur_len())
+    }
+
+    fn get(&self, idx: &usize) -> Option<(A, B)> {
+        Some((self.a.get(*idx)?.clone(), self.b.get(*idx)?.clone()))
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::vec::*;
+    use crate::view::port::UpdateTask;
+
+    #[test]
+    fn zip_list1() {
+        let mut a = VecBuffer::with_data(vec![1, 2, 3]);
+        let mut b = VecBuffer::with_data(vec!['a', 'b']);
+        let target_port = a.get_port().to_list().zip(&b.get_port().to_list());
+
+        let mirror = VecBuffer::<(i32, char)>::new();
+        let _keepalive = mirror.attach_to(target_port.clone());
+        assert_eq!(mirror.len(), 2);
+
+        b.push('c');
+        a.insert(0, 0);
+        b.update(0, 'x');
+        a.remove(3);
+
+        target_port.0.update();
+        mirror.get_port().0.update();
+
+        assert_eq!(
+            *mirror.get_port().get_view().unwrap().read().unwrap(),
+            vec![(0, 'x'), (1, 'b'), (2, 'c')]
+        );
+        assert_eq!(target_port.get_view().unwrap().get(&3), None);
+    }
+}
@@ -0,0 +1,295 @@
+use {
+    crate::{
+        buffer::{
+            index_hashmap::IndexBuffer,
+            singleton::SingletonBuffer,
+            vec::{VecBuffer, VecDiff},
+        },
+        projection::{
+            serialize_diff::*,
+            view2bin::{apply_bin_sync, read_frame_sync},
+        },
+        remote::{Connection, RemoteError, RemoteKind},
+        view::list::ListDiff,
+    },
+    serde::de::DeserializeOwned,
+    std::{
+        hash::Hash,
+        io::{Read, Write},
+        net::{SocketAddr, TcpStream},
+        thread::{self, JoinHandle},
+    },
+};
+
+#[cfg(unix)]
+use std::{
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+enum RemoteAddr {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Subscribes to views published by a `RemoteViewServer`
+pub struct RemoteViewClient {
+    addr: RemoteAddr,
+}
+
+impl RemoteViewClient {
+    pub fn tcp(addr: SocketAddr) -> Self {
+        RemoteViewClient {
+            addr: RemoteAddr::Tcp(addr),
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn unix(path: impl AsRef<Path>) -> Self {
+        RemoteViewClient {
+            addr: RemoteAddr::Unix(path.as_ref().into()),
+        }
+    }
+
+    /// connect and request the view `name`, returns its kind
+    fn subscribe(&self, name: &str) -> Result<(Connection, RemoteKind), RemoteError> {
+        let mut conn = match &self.addr {
+            RemoteAddr::Tcp(addr) => Connection::Tcp(TcpStream::connect(addr)?),
+            #[cfg(unix)]
+            RemoteAddr::Unix(path) => Connection::Unix(UnixStream::connect(path)?),
+        };
+
+        conn.write_all(name.as_bytes())?;
+        conn.write_all(b"\n")?;
+
+        let mut kind = [0u8];
+        conn.read_exact(&mut kind)?;
+        match RemoteKind::from_byte(kind[0]) {
+            Some(kind) => Ok((conn, kind)),
+            None => Err(RemoteError::UnknownView(name.into())),
+        }
+    }
+
+    /// materialize a published vec, list or sequence
+    pub fn vec<T>(&self, name: &str) -> Result<RemoteView<VecBuffer<T>>, RemoteError>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let (conn, kind) = self.subscribe(name)?;
+        let buffer = VecBuffer::new();
+        match kind {
+            RemoteKind::Vec => RemoteView::spawn::<VecDiff<T>>(buffer, conn),
+            RemoteKind::List => RemoteView::spawn::<ListDiff<T>>(buffer, conn),
+            RemoteKind::Sequence => RemoteView::spawn::<SequenceUpdate<T>>(buffer, conn),
+            kind => Err(RemoteError::KindMismatch { name: name.into(), kind }),
+        }
+    }
+
+    /// materialize a published singleton,
+    /// blocks until its initial value is received
+    pub fn singleton<T>(&self, name: &str) -> Result<RemoteView<SingletonBuffer<T>>, RemoteError>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let (mut conn, kind) = self.subscribe(name)?;
+        if kind != RemoteKind::Singleton {
+            return Err(RemoteError::KindMismatch { name: name.into(), kind });
+        }
+
+        match read_frame_sync::<T, _>(&mut conn, 1)? {
+            Some(value) => RemoteView::spawn::<T>(SingletonBuffer::new(value), conn),
+            None => Err(RemoteError::Decode(DiffDecodeError::Truncated { frame: 1 })),
+        }
+    }
+
+    /// materialize a published index view
+    pub fn index<Key, T>(&self, name: &str) -> Result<RemoteView<IndexBuffer<Key, T>>, RemoteError>
+    where
+        Key: DeserializeOwned + Clone + Hash + Eq + Send + Sync + 'static,
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let (conn, kind) = self.subscribe(name)?;
+        if kind != RemoteKind::Index {
+            return Err(RemoteError::KindMismatch { name: name.into(), kind });
+        }
+
+        RemoteView::spawn::<IndexUpdate<Key, T>>(IndexBuffer::new(), conn)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Buffer which mirrors a remote view.
+/// Dropping it closes the connection.
+pub struct RemoteView<B> {
+    pub buffer: B,
+    conn: Connection,
+    receiver: Option<JoinHandle<Result<(), DiffDecodeError>>>,
+}
+
+impl<B> RemoteView<B>
+where
+    B: Clone + Send + 'static,
+{
+    fn spawn<D>(buffer: B, conn: Connection) -> Result<Self, RemoteError>
+    where
+        D: DeserializeOwned,
+        B: ApplyDiff<D>,
+    {
+        let mut target = buffer.clone();
+        let read = conn.try_clone()?;
+
+        Ok(RemoteView {
+            buffer,
+            conn,
+            receiver: Some(thread::spawn(move || {
+                apply_bin_sync::<D, _, _>(&mut target, read)
+            })),
+        })
+    }
+
+    /// wait until the server closes the connection
+    pub fn join(mut self) -> Result<(), DiffDecodeError> {
+        self.receiver
+            .take()
+            .expect("receiver is only taken once")
+            .join()
+            .expect("receiver thread panicked")
+    }
+}
+
+impl<B> Drop for RemoteView<B> {
+    fn drop(&mut self) {
+        self.conn.shutdown();
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            buffer::{index_hashmap::*, singleton::*, vec::*},
+            remote::*,
+        },
+        std::{io::{Read, Write}, net::TcpStream, time::Duration},
+    };
+
+    fn wait_for(cond: impl Fn() -> bool) {
+        for _ in 0..2000 {
+            if cond() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("timeout");
+    }
+
+    #[test]
+    fn remote_tcp() {
+        let mut list = VecBuffer::<i32>::with_data(vec![1, 2]);
+        let mut singleton = SingletonBuffer::new('a');
+        let mut index = IndexBuffer::<u8, String>::new();
+
+        let server = RemoteViewServer::new();
+        server.publish_vec("vec", list.get_port());
+        server.publish_sequence("seq", list.get_port().to_sequence().map(|x| x * 2));
+        server.publish_singleton("singleton", singleton.get_port());
+        server.publish_index("index", index.get_port());
+        let listener = server.listen_tcp("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = RemoteViewClient::tcp(addr);
+        let vec = client.vec::<i32>("vec").unwrap();
+        let seq = client.vec::<i32>("seq").unwrap();
+        let sgl = client.singleton::<char>("singleton").unwrap();
+        let idx = client.index::<u8, String>("index").unwrap();
+
+        wait_for(|| vec.buffer.len() == 2 && seq.buffer.len() == 2);
+        assert_eq!(seq.buffer.get(1), 4);
+        assert_eq!(sgl.buffer.get(), 'a');
+
+        list.push(3);
+        list.remove(0);
+        singleton.set('b');
+        index.insert(7, "x".into());
+
+        wait_for(|| vec.buffer.len() == 2 && vec.buffer.get(1) == 3);
+        wait_for(|| seq.buffer.len() == 2 && seq.buffer.get(1) == 6);
+        wait_for(|| sgl.buffer.get() == 'b');
+        wait_for(|| idx.buffer.get(&7).is_some());
+        assert_eq!(idx.buffer.get(&7), Some("x".to_string()));
+
+        assert!(matches!(client.vec::<i32>("nope"), Err(RemoteError::UnknownView(_))));
+        assert!(matches!(
+            client.vec::<i32>("singleton"),
+            Err(RemoteError::KindMismatch { kind: RemoteKind::Singleton, .. })
+        ));
+
+        listener.shutdown();
+        assert!(TcpStream::connect(addr).is_err());
+    }
+
+    #[test]
+    fn remote_stalled_subscriber() {
+        let mut list = VecBuffer::<String>::new();
+        let mut server = RemoteViewServer::new();
+        server.set_send_timeout(Duration::from_secs(1));
+        server.publish_vec("vec", list.get_port());
+        let listener = server.listen_tcp("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // subscribes but never reads the diffs
+        let mut stalled = TcpStream::connect(addr).unwrap();
+        stalled.write_all(b"vec\n").unwrap();
+        stalled.read_exact(&mut [0]).unwrap();
+
+        let client = RemoteViewClient::tcp(addr);
+        let remote = client.vec::<String>("vec").unwrap();
+
+        for _ in 0..512 {
+            list.push("x".repeat(1 << 16));
+        }
+        wait_for(|| remote.buffer.len() == 512);
+
+        // the stalled subscriber is disconnected once its queue stays full
+        stalled.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        let mut received = Vec::new();
+        match stalled.read_to_end(&mut received) {
+            Ok(_) => assert!(received.len() < 512 << 16),
+            Err(err) => assert_eq!(err.kind(), std::io::ErrorKind::ConnectionReset),
+        }
+
+        listener.shutdown();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn remote_unix() {
+        let path = std::env::temp_dir().join(format!("r3vi-remote-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut list = VecBuffer::<char>::new();
+        let server = RemoteViewServer::new();
+        server.publish_list("list", list.get_port().to_list());
+        let listener = server.listen_unix(&path).unwrap();
+
+        list.push('a');
+
+        let client = RemoteViewClient::unix(&path);
+        let remote = client.vec::<char>("list").unwrap();
+        wait_for(|| remote.buffer.len() == 1);
+
+        list.insert(0, 'b');
+        wait_for(|| remote.buffer.len() == 2);
+        assert_eq!(remote.buffer.get(0), 'b');
+
+        drop(remote);
+        listener.shutdown();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+//! Replication of views between processes.
+//!
+//! A `RemoteViewServer` publishes named ports over TCP or a Unix domain socket.
+//! A `RemoteViewClient` subscribes to them by name and materializes
+//! each view as a buffer which is kept up to date by the incoming diffs.
+//!
+//! Protocol: the client sends the name of the view followed by a newline,
+//! the server answers with one `RemoteKind` byte (`0` if the name is unknown)
+//! and then streams the length-prefixed bincode diffs of the view,
+//! beginning with a snapshot of its current state.
+//! A subscriber which does not keep up with the diffs is disconnected.
+
+pub mod server;
+pub mod client;
+
+pub use {client::RemoteViewClient, server::{ListenerHandle, RemoteViewServer}};
+
+use {
+    crate::projection::serialize_diff::DiffDecodeError,
+    std::{
+        fmt,
+        io::{self, Read, Write},
+        net::{Shutdown, TcpStream},
+    },
+};
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// view kind of a published port, which determines the diff format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoteKind {
+    Vec = 1,
+    List = 2,
+    Sequence = 3,
+    Singleton = 4,
+    Index = 5,
+}
+
+impl RemoteKind {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            1 => Some(RemoteKind::Vec),
+            2 => Some(RemoteKind::List),
+            3 => Some(RemoteKind::Sequence),
+            4 => Some(RemoteKind::Singleton),
+            5 => Some(RemoteKind::Index),
+            _ => None,
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[derive(Debug)]
+pub enum RemoteError {
+    Io(io::Error),
+
+    /// the server does not publish a view with this name
+    UnknownView(String),
+
+    /// the published view can not be materialized as the requested buffer
+    KindMismatch { name: String, kind: RemoteKind },
+
+    /// the initial snapshot could not be decoded
+    Decode(DiffDecodeError),
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RemoteError::Io(err) => write!(f, "connection error: {}", err),
+            RemoteError::UnknownView(name) => write!(f, "unknown view '{}'", name),
+            RemoteError::KindMismatch { name, kind } => {
+                write!(f, "view '{}' is of incompatible kind {:?}", name, kind)
+            }
+            RemoteError::Decode(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+impl From<io::Error> for RemoteError {
+    fn from(err: io::Error) -> Self {
+        RemoteError::Io(err)
+    }
+}
+
+impl From<DiffDecodeError> for RemoteError {
+    fn from(err: DiffDecodeError) -> Self {
+        RemoteError::Decode(err)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// socket of either transport
+pub(crate) enum Connection {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Connection {
+    pub(crate) fn try_clone(&self) -> io::Result<Self> {
+        Ok(match self {
+            Connection::Tcp(s) => Connection::Tcp(s.try_clone()?),
+            #[cfg(unix)]
+            Connection::Unix(s) => Connection::Unix(s.try_clone()?),
+        })
+    }
+
+    pub(crate) fn shutdown(&self) {
+        // the peer may already be gone
+        let _ = match self {
+            Connection::Tcp(s) => s.shutdown(Shutdown::Both),
+            #[cfg(unix)]
+            Connection::Unix(s) => s.shutdown(Shutdown::Both),
+        };
+    }
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Connection::Tcp(s) => s.read(buf),
+            #[cfg(unix)]
+            Connection::Unix(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Connection::Tcp(s) => s.write(buf),
+            #[cfg(unix)]
+            Connection::Unix(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Connection::Tcp(s) => s.flush(),
+            #[cfg(unix)]
+            Connection::Unix(s) => s.flush(),
+        }
+    }
+}
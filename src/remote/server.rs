@@ -0,0 +1,345 @@
+use {
+    crate::{
+        projection::serialize_diff::*,
+        remote::{Connection, RemoteKind},
+        view::{
+            index::IndexView, list::ListView, sequence::SequenceView, singleton::SingletonView,
+            OuterViewPort, View,
+        },
+    },
+    serde::Serialize,
+    std::{
+        any::Any,
+        collections::HashMap,
+        hash::Hash,
+        io::{self, BufRead, BufReader, Read, Write},
+        net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc::{self, TrySendError},
+            Arc, Mutex, RwLock,
+        },
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+#[cfg(unix)]
+use std::{
+    os::unix::net::{UnixListener, UnixStream},
+    path::{Path, PathBuf},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// registers a writer for the connection
+/// and returns everything that has to be kept alive
+type Subscribe = Box<dyn Fn(FrameQueue) -> Box<dyn Any + Send + Sync> + Send + Sync>;
+
+/// number of flushed batches of frames which may wait for a subscriber
+pub const QUEUE_LEN: usize = 64;
+
+/// Collects the frames of one update and passes them
+/// to the sending thread of the connection on flush.
+///
+/// The queue is bounded, if it stays full for longer than
+/// the send timeout, the subscriber is disconnected
+/// and has to subscribe again to get a new snapshot.
+struct FrameQueue {
+    buf: Vec<u8>,
+    tx: mpsc::SyncSender<Vec<u8>>,
+    conn: Connection,
+    timeout: Duration,
+}
+
+impl FrameQueue {
+    fn new(conn: Connection, timeout: Duration) -> (Self, mpsc::Receiver<Vec<u8>>) {
+        let (tx, rx) = mpsc::sync_channel(QUEUE_LEN);
+        (FrameQueue { buf: Vec::new(), tx, conn, timeout }, rx)
+    }
+
+    /// write the queued frames to `conn` from a new thread
+    fn spawn_sender(mut conn: Connection, frames: mpsc::Receiver<Vec<u8>>) -> JoinHandle<()> {
+        thread::spawn(move || {
+            for frames in frames {
+                if conn.write_all(&frames).is_err() {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+impl Write for FrameQueue {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        let deadline = Instant::now() + self.timeout;
+        let mut frames = std::mem::take(&mut self.buf);
+        loop {
+            match self.tx.try_send(frames) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Full(f)) if Instant::now() < deadline => {
+                    frames = f;
+                    thread::sleep(Duration::from_millis(1));
+                }
+                Err(TrySendError::Full(_)) => {
+                    self.conn.shutdown();
+                    return Err(io::Error::new(io::ErrorKind::TimedOut, "subscriber does not keep up"));
+                }
+                Err(TrySendError::Disconnected(_)) => return Err(io::ErrorKind::BrokenPipe.into()),
+            }
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+struct Publication {
+    kind: RemoteKind,
+    subscribe: Subscribe,
+}
+
+/// Publishes named ports to `RemoteViewClient`s.
+///
+/// Every subscriber gets its own serialization observer
+/// and an updater which pushes the diffs as soon as they occur.
+/// The frames are queued and written by a thread per connection,
+/// a subscriber which does not keep up is disconnected.
+#[derive(Clone)]
+pub struct RemoteViewServer {
+    ports: Arc<RwLock<HashMap<String, Publication>>>,
+    send_timeout: Duration,
+}
+
+impl Default for RemoteViewServer {
+    fn default() -> Self {
+        RemoteViewServer {
+            ports: Arc::new(RwLock::new(HashMap::new())),
+            send_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RemoteViewServer {
+    pub fn new() -> Self {
+        RemoteViewServer::default()
+    }
+
+    /// how long an update waits for a full queue
+    /// before its subscriber is disconnected
+    pub fn set_send_timeout(&mut self, timeout: Duration) {
+        self.send_timeout = timeout;
+    }
+
+    fn publish_with<V, E>(&self, name: &str, kind: RemoteKind, port: OuterViewPort<V>, encoder: fn() -> E)
+    where
+        V: View + ?Sized + 'static,
+        V::Msg: Clone,
+        E: DiffEncoder<View = V> + 'static,
+        E::Diff: Serialize,
+    {
+        let subscribe: Subscribe = Box::new(move |queue| {
+            let writer = port.serialize_bin_with(encoder(), queue, None);
+            Box::new((writer, port.spawn_updater()))
+        });
+
+        self.ports
+            .write()
+            .unwrap()
+            .insert(name.into(), Publication { kind, subscribe });
+    }
+
+    pub fn publish_vec<T>(&self, name: &str, port: OuterViewPort<RwLock<Vec<T>>>)
+    where
+        T: Clone + Serialize + Send + Sync + 'static,
+    {
+        self.publish_with(name, RemoteKind::Vec, port, VecEncoder::default);
+    }
+
+    pub fn publish_list<T>(&self, name: &str, port: OuterViewPort<dyn ListView<T>>)
+    where
+        T: Clone + Serialize + Send + Sync + 'static,
+    {
+        self.publish_with(name, RemoteKind::List, port, ListEncoder::default);
+    }
+
+    pub fn publish_sequence<T>(&self, name: &str, port: OuterViewPort<dyn SequenceView<Item = T>>)
+    where
+        T: Clone + Serialize + Send + Sync + 'static,
+    {
+        self.publish_with(name, RemoteKind::Sequence, port, SequenceEncoder::default);
+    }
+
+    pub fn publish_singleton<T>(&self, name: &str, port: OuterViewPort<dyn SingletonView<Item = T>>)
+    where
+        T: Clone + Serialize + Send + Sync + 'static,
+    {
+        self.publish_with(name, RemoteKind::Singleton, port, SingletonEncoder::default);
+    }
+
+    pub fn publish_index<Key, T>(&self, name: &str, port: OuterViewPort<dyn IndexView<Key, Item = T>>)
+    where
+        Key: Clone + Hash + Eq + Serialize + Send + Sync + 'static,
+        T: Clone + Serialize + Send + Sync + 'static,
+    {
+        self.publish_with(name, RemoteKind::Index, port, IndexEncoder::default);
+    }
+
+    /// stop publishing `name` for new subscribers
+    pub fn unpublish(&self, name: &str) {
+        self.ports.write().unwrap().remove(name);
+    }
+
+    /// accept connections on a TCP socket in a background thread
+    pub fn listen_tcp(&self, addr: impl ToSocketAddrs) -> io::Result<ListenerHandle> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+
+        Ok(self.spawn_listener(
+            move || Ok(Connection::Tcp(listener.accept()?.0)),
+            Box::new(move || {
+                let _ = TcpStream::connect(local_addr);
+            }),
+            Some(local_addr),
+        ))
+    }
+
+    /// accept connections on a Unix domain socket in a background thread
+    #[cfg(unix)]
+    pub fn listen_unix(&self, path: impl AsRef<Path>) -> io::Result<ListenerHandle> {
+        let listener = UnixListener::bind(path.as_ref())?;
+        let path: PathBuf = path.as_ref().into();
+
+        Ok(self.spawn_listener(
+            move || Ok(Connection::Unix(listener.accept()?.0)),
+            Box::new(move || {
+                let _ = UnixStream::connect(&path);
+            }),
+            None,
+        ))
+    }
+
+    fn spawn_listener(
+        &self,
+        mut accept: impl FnMut() -> io::Result<Connection> + Send + 'static,
+        wake: Box<dyn Fn() + Send + Sync>,
+        local_addr: Option<SocketAddr>,
+    ) -> ListenerHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+        let conns: Connections = Arc::new(Mutex::new(Vec::new()));
+
+        let server = self.clone();
+        let accept_thread = thread::spawn({
+            let stop = stop.clone();
+            let conns = conns.clone();
+            move || {
+                while !stop.load(Ordering::SeqCst) {
+                    let Ok(conn) = accept() else { continue };
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(peer) = conn.try_clone() else { continue };
+
+                    let server = server.clone();
+                    let thread = thread::spawn(move || {
+                        // the subscriber disconnected, nothing left to do
+                        let _ = server.serve(conn);
+                    });
+
+                    let mut conns = conns.lock().unwrap();
+                    conns.retain(|(_, thread)| !thread.is_finished());
+                    conns.push((peer, thread));
+                }
+            }
+        });
+
+        ListenerHandle {
+            local_addr,
+            stop,
+            wake,
+            accept_thread,
+            conns,
+        }
+    }
+
+    fn serve(&self, mut conn: Connection) -> io::Result<()> {
+        let mut reader = BufReader::new(conn.try_clone()?);
+        let mut name = String::new();
+        reader.read_line(&mut name)?;
+        let name = name.trim_end_matches('\n');
+
+        let (subscription, sender) = {
+            let ports = self.ports.read().unwrap();
+            match ports.get(name) {
+                Some(publication) => {
+                    // acknowledge the kind only once the writer is attached,
+                    // so every change after the acknowledgement is sent as a diff
+                    let (queue, frames) = FrameQueue::new(conn.try_clone()?, self.send_timeout);
+                    let subscription = (publication.subscribe)(queue);
+                    conn.write_all(&[publication.kind as u8])?;
+                    (subscription, FrameQueue::spawn_sender(conn.try_clone()?, frames))
+                }
+                None => {
+                    conn.write_all(&[0])?;
+                    return Ok(());
+                }
+            }
+        };
+
+        // the subscriber never sends anything else,
+        // so wait until it closes the connection
+        let mut buf = [0u8; 64];
+        while matches!(reader.read(&mut buf), Ok(n) if n > 0) {}
+
+        // the queue is dropped with the writer, which ends the sender
+        conn.shutdown();
+        drop(subscription);
+        let _ = sender.join();
+        Ok(())
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+type Connections = Arc<Mutex<Vec<(Connection, JoinHandle<()>)>>>;
+
+/// Threads of one listener of a `RemoteViewServer`.
+/// Dropping the handle keeps them running, `shutdown()` stops them.
+pub struct ListenerHandle {
+    local_addr: Option<SocketAddr>,
+    stop: Arc<AtomicBool>,
+
+    /// connects to the listener, so a blocked accept returns
+    wake: Box<dyn Fn() + Send + Sync>,
+
+    accept_thread: JoinHandle<()>,
+    conns: Connections,
+}
+
+impl ListenerHandle {
+    /// bound address of a TCP listener
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// stop accepting, disconnect all subscribers and join all threads
+    pub fn shutdown(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        (self.wake)();
+        let _ = self.accept_thread.join();
+
+        let conns = std::mem::take(&mut *self.conns.lock().unwrap());
+        for (conn, thread) in conns {
+            conn.shutdown();
+            let _ = thread.join();
+        }
+    }
+}
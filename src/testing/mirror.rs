@@ -0,0 +1,240 @@
+use {
+    crate::view::{
+        Observer, View,
+        index::{IndexArea, IndexView},
+        list::{ListDiff, ListView, ListViewExt},
+        sequence::{SeqDiff, SeqDiffBatch, SequenceView, SequenceViewExt},
+    },
+    std::{collections::HashMap, fmt::Debug, hash::Hash, sync::Arc},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Views whose contents can be replicated from their notifications
+pub trait Mirror: View
+where
+    Self::Msg: Clone,
+{
+    type Contents: PartialEq + Debug;
+    type Observer: Observer<Self> + Default + 'static;
+
+    /// contents as replicated by the observer,
+    /// or a description of an invalid notification
+    fn mirrored(observer: &Self::Observer) -> Result<Self::Contents, String>;
+
+    /// contents as read from the view
+    fn evaluate(view: &Arc<Self>) -> Self::Contents;
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Keeps a copy of the sequence which is only updated
+/// at the notified indices, reading them when the update is complete.
+pub struct SequenceMirror<T: 'static> {
+    view: Option<Arc<dyn SequenceView<Item = T>>>,
+    items: Vec<T>,
+    pending: SeqDiffBatch,
+    error: Option<String>,
+}
+
+impl<T: 'static> Default for SequenceMirror<T> {
+    fn default() -> Self {
+        SequenceMirror { view: None, items: Vec::new(), pending: SeqDiffBatch::default(), error: None }
+    }
+}
+
+impl<T: 'static> SequenceMirror<T> {
+    fn read(&mut self, idx: usize) -> Option<T> {
+        let val = self.view.as_ref()?.get(&idx);
+        if val.is_none() && self.error.is_none() {
+            self.error = Some(format!("notified index {} is not in the view", idx));
+        }
+        val
+    }
+}
+
+impl<T: Send + Sync + 'static> Observer<dyn SequenceView<Item = T>> for SequenceMirror<T> {
+    fn reset(&mut self, view: Option<Arc<dyn SequenceView<Item = T>>>) {
+        self.items = view.iter().flat_map(|v| v.iter()).collect();
+        self.view = view;
+        self.pending = SeqDiffBatch::default();
+    }
+
+    fn notify(&mut self, diff: &SeqDiff) {
+        self.pending.push(diff.clone());
+    }
+
+    fn end_update(&mut self) {
+        for diff in std::mem::take(&mut self.pending) {
+            match diff {
+                SeqDiff::Range(r) => {
+                    for idx in r {
+                        match (idx < self.items.len(), self.read(idx)) {
+                            (true, Some(val)) => self.items[idx] = val,
+                            _ => self.error = Some(format!("notified index {} is out of range", idx)),
+                        }
+                    }
+                }
+                SeqDiff::Shift { at, by } if by > 0 && at <= self.items.len() => {
+                    for idx in at..at + by as usize {
+                        if let Some(val) = self.read(idx) {
+                            self.items.insert(idx, val);
+                        }
+                    }
+                }
+                SeqDiff::Shift { at, by } if at + by.unsigned_abs() <= self.items.len() => {
+                    self.items.drain(at..at + by.unsigned_abs());
+                }
+                diff => self.error = Some(format!("{:?} is out of range", diff)),
+            }
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + Debug + Send + Sync + 'static> Mirror for dyn SequenceView<Item = T> {
+    type Contents = Vec<T>;
+    type Observer = SequenceMirror<T>;
+
+    fn mirrored(observer: &SequenceMirror<T>) -> Result<Vec<T>, String> {
+        match &observer.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(observer.items.clone()),
+        }
+    }
+
+    fn evaluate(view: &Arc<Self>) -> Vec<T> {
+        view.iter().collect()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Applies the notified diffs to a copy of the list
+pub struct ListMirror<T> {
+    items: Vec<T>,
+    error: Option<String>,
+}
+
+impl<T> Default for ListMirror<T> {
+    fn default() -> Self {
+        ListMirror { items: Vec::new(), error: None }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Observer<dyn ListView<T>> for ListMirror<T> {
+    fn reset(&mut self, view: Option<Arc<dyn ListView<T>>>) {
+        self.items = view.iter().flat_map(|v| v.iter()).collect();
+    }
+
+    fn notify(&mut self, diff: &ListDiff<T>) {
+        let len = self.items.len();
+        match diff.clone() {
+            ListDiff::Clear => self.items.clear(),
+            ListDiff::Insert { idx, val } if idx <= len => self.items.insert(idx, val),
+            ListDiff::Remove(idx) if idx < len => {
+                self.items.remove(idx);
+            }
+            ListDiff::Update { idx, val } if idx < len => self.items[idx] = val,
+            _ => self.error = Some(format!("notified index is out of range (length {})", len)),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + Debug + Send + Sync + 'static> Mirror for dyn ListView<T> {
+    type Contents = Vec<T>;
+    type Observer = ListMirror<T>;
+
+    fn mirrored(observer: &ListMirror<T>) -> Result<Vec<T>, String> {
+        match &observer.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(observer.items.clone()),
+        }
+    }
+
+    fn evaluate(view: &Arc<Self>) -> Vec<T> {
+        view.iter().collect()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Keeps a copy of all items, re-reading the notified keys
+/// when the update is complete.
+/// The area of the view has to be an `IndexArea::Set`.
+pub struct IndexMirror<Key, T>
+where
+    Key: Send + Sync + 'static,
+    T: 'static,
+{
+    view: Option<Arc<dyn IndexView<Key, Item = T>>>,
+    items: HashMap<Key, T>,
+    dirty: Vec<Key>,
+    all_dirty: bool,
+}
+
+impl<Key: Send + Sync, T> Default for IndexMirror<Key, T> {
+    fn default() -> Self {
+        IndexMirror { view: None, items: HashMap::new(), dirty: Vec::new(), all_dirty: false }
+    }
+}
+
+fn index_keys<Key: Clone + Send + Sync, T>(view: &Arc<dyn IndexView<Key, Item = T>>) -> Vec<Key> {
+    match view.area() {
+        IndexArea::Set(keys) => keys,
+        IndexArea::Empty => vec![],
+        area => panic!("can not enumerate the keys of {:?}", area.map(|_| ())),
+    }
+}
+
+impl<Key, T> Observer<dyn IndexView<Key, Item = T>> for IndexMirror<Key, T>
+where
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    fn reset(&mut self, view: Option<Arc<dyn IndexView<Key, Item = T>>>) {
+        self.view = view;
+        self.items.clear();
+        self.all_dirty = true;
+        self.end_update();
+    }
+
+    fn notify(&mut self, area: &IndexArea<Key>) {
+        match area {
+            IndexArea::Empty => {}
+            IndexArea::Set(keys) => self.dirty.extend(keys.iter().cloned()),
+            _ => self.all_dirty = true,
+        }
+    }
+
+    fn end_update(&mut self) {
+        let mut keys = std::mem::take(&mut self.dirty);
+        if std::mem::take(&mut self.all_dirty) {
+            keys.extend(self.items.keys().cloned());
+            keys.extend(self.view.iter().flat_map(index_keys));
+        }
+
+        for key in keys {
+            match self.view.as_ref().and_then(|v| v.get(&key)) {
+                Some(val) => self.items.insert(key, val),
+                None => self.items.remove(&key),
+            };
+        }
+    }
+}
+
+impl<Key, T> Mirror for dyn IndexView<Key, Item = T>
+where
+    Key: Clone + Hash + Eq + Debug + Send + Sync + 'static,
+    T: Clone + PartialEq + Debug + Send + Sync + 'static,
+{
+    type Contents = HashMap<Key, T>;
+    type Observer = IndexMirror<Key, T>;
+
+    fn mirrored(observer: &IndexMirror<Key, T>) -> Result<HashMap<Key, T>, String> {
+        Ok(observer.items.clone())
+    }
+
+    fn evaluate(view: &Arc<Self>) -> HashMap<Key, T> {
+        index_keys(view).into_iter().filter_map(|key| Some((key.clone(), view.get(&key)?))).collect()
+    }
+}
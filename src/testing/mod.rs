@@ -0,0 +1,449 @@
+//! Randomized tests of projections.
+//!
+//! A projection is built on top of a buffer which is modified by random operations.
+//! After every update, the contents replicated from the notifications of the projection
+//! (see `Mirror`) are compared to a fresh projection of a copy of the buffer.
+//! All random choices are made by a seeded generator, so every failure can be reproduced.
+//!
+//!# Examples
+//!
+//! ```
+//! use r3vi::{
+//!     testing::*,
+//!     view::{sequence::*, OuterViewPort},
+//! };
+//!
+//! check(
+//!     &Config::default(),
+//!     vec_ops(|rng| rng.below(10) as u8),
+//!     |port: &OuterViewPort<_>| port.to_sequence().filter(|x| x % 2 == 0),
+//! );
+//! ```
+
+pub mod mirror;
+
+#[cfg(test)]
+pub(crate) mod shared_buf;
+
+pub use mirror::{IndexMirror, ListMirror, Mirror, SequenceMirror};
+
+use {
+    crate::{
+        buffer::{index_hashmap::IndexBuffer, vec::{VecBuffer, VecDiff}},
+        view::{OuterViewPort, View, port::UpdateTask, index::{IndexArea, IndexView}},
+    },
+    std::sync::RwLock,
+    std::{fmt::Debug, hash::Hash, sync::Arc},
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Deterministic pseudo random generator (splitmix64)
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// number in `0..n`, `n` must not be zero
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Buffer which can be modified by random operations
+pub trait Source: Sized {
+    type Op: Clone + Debug;
+    type Msg: Clone + Send + Sync;
+    type View: View<Msg = Self::Msg> + ?Sized + 'static;
+
+    fn new() -> Self;
+    fn port(&self) -> OuterViewPort<Self::View>;
+
+    /// buffer with the same contents, independent of this one
+    fn copy(&self) -> Self;
+
+    fn apply(&mut self, op: &Self::Op);
+}
+
+/// Modification of a `VecBuffer`.
+/// Indices are taken modulo the length when the operation is applied,
+/// so every sequence of operations is valid.
+#[derive(Clone, Debug)]
+pub enum VecOp<T> {
+    Push(T),
+    Insert(usize, T),
+    Remove(usize),
+    Update(usize, T),
+    Clear,
+}
+
+impl<T: Clone + Debug + Send + Sync + 'static> Source for VecBuffer<T> {
+    type Op = VecOp<T>;
+    type Msg = VecDiff<T>;
+    type View = RwLock<Vec<T>>;
+
+    fn new() -> Self {
+        VecBuffer::new()
+    }
+
+    fn port(&self) -> OuterViewPort<RwLock<Vec<T>>> {
+        self.get_port()
+    }
+
+    fn copy(&self) -> Self {
+        VecBuffer::with_data(self.get_port().get_view().unwrap().read().unwrap().clone())
+    }
+
+    fn apply(&mut self, op: &VecOp<T>) {
+        let len = self.len();
+        match op.clone() {
+            VecOp::Push(val) => self.push(val),
+            VecOp::Insert(idx, val) => self.insert(idx % (len + 1), val),
+            VecOp::Remove(idx) if len > 0 => self.remove(idx % len),
+            VecOp::Update(idx, val) if len > 0 => self.update(idx % len, val),
+            VecOp::Clear => self.clear(),
+            _ => {}
+        }
+    }
+}
+
+/// random operations on a `VecBuffer`, which mostly insert
+/// items while it is short and mostly remove items while it is long
+pub fn vec_ops<T>(item: impl Fn(&mut Rng) -> T) -> impl Fn(&mut Rng, &VecBuffer<T>) -> VecOp<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    move |rng, buffer| {
+        let idx = rng.next_u64() as usize;
+        match rng.below(32) {
+            0 => VecOp::Clear,
+            n if n < 8 => VecOp::Push(item(rng)),
+            n if n < 20 => {
+                if rng.below(16) < buffer.len() {
+                    VecOp::Remove(idx)
+                } else {
+                    VecOp::Insert(idx, item(rng))
+                }
+            }
+            _ => VecOp::Update(idx, item(rng)),
+        }
+    }
+}
+
+/// Modification of an `IndexBuffer`
+#[derive(Clone, Debug)]
+pub enum IndexOp<Key, T> {
+    Insert(Key, T),
+    Remove(Key),
+}
+
+impl<Key, T> Source for IndexBuffer<Key, T>
+where
+    Key: Clone + Hash + Eq + Debug + Send + Sync + 'static,
+    T: Clone + Debug + Send + Sync + 'static,
+{
+    type Op = IndexOp<Key, T>;
+    type Msg = IndexArea<Key>;
+    type View = dyn IndexView<Key, Item = T>;
+
+    fn new() -> Self {
+        IndexBuffer::new()
+    }
+
+    fn port(&self) -> OuterViewPort<dyn IndexView<Key, Item = T>> {
+        self.get_port()
+    }
+
+    fn copy(&self) -> Self {
+        let view = self.get_port().get_view().unwrap();
+        match view.area() {
+            IndexArea::Set(keys) => keys.into_iter()
+                .filter_map(|key| Some((key.clone(), view.get(&key)?)))
+                .collect(),
+            _ => IndexBuffer::new(),
+        }
+    }
+
+    fn apply(&mut self, op: &IndexOp<Key, T>) {
+        match op.clone() {
+            IndexOp::Insert(key, val) => self.insert(key, val),
+            IndexOp::Remove(key) => self.remove(key),
+        }
+    }
+}
+
+/// random insertions and removals on an `IndexBuffer`
+pub fn index_ops<Key, T>(
+    key: impl Fn(&mut Rng) -> Key,
+    item: impl Fn(&mut Rng) -> T,
+) -> impl Fn(&mut Rng, &IndexBuffer<Key, T>) -> IndexOp<Key, T>
+where
+    Key: Clone + Hash + Eq + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    move |rng, _buffer| {
+        if rng.below(3) == 0 {
+            IndexOp::Remove(key(rng))
+        } else {
+            IndexOp::Insert(key(rng), item(rng))
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub seed: u64,
+
+    /// number of updates
+    pub steps: usize,
+
+    /// maximal number of operations between two updates
+    pub batch: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { seed: 0, steps: 200, batch: 4 }
+    }
+}
+
+/// Divergence between the notifications of a projection
+/// and a fresh evaluation
+#[derive(Debug)]
+pub struct Failure<Op, Contents> {
+    /// the operations applied before each update
+    pub batches: Vec<Vec<Op>>,
+    pub mirrored: Result<Contents, String>,
+    pub live: Contents,
+    pub expected: Contents,
+}
+
+/// Apply random operations to a source buffer and check the projection
+/// built by `build` after every update.
+/// On failure, the operations are shrunk to a minimal failing sequence
+/// before panicking with the resulting `Failure`.
+pub fn check<S, V, G, F>(config: &Config, gen_op: G, build: F)
+where
+    S: Source,
+    V: Mirror + ?Sized + 'static,
+    V::Msg: Clone,
+    G: Fn(&mut Rng, &S) -> S::Op,
+    F: Fn(&OuterViewPort<S::View>) -> OuterViewPort<V>,
+{
+    let mut rng = Rng::new(config.seed);
+    let mut run = Run::<S, V>::new(&build);
+    let mut batches = Vec::new();
+
+    for _ in 0..config.steps {
+        let batch: Vec<_> = (0..=rng.below(config.batch.max(1)))
+            .map(|_| {
+                let op = gen_op(&mut rng, &run.src);
+                run.src.apply(&op);
+                op
+            })
+            .collect();
+        batches.push(batch);
+
+        if run.step(&build).is_err() {
+            let failure = shrink::<S, V, F>(batches, &build);
+            panic!("projection diverged (seed {}): {:#?}", config.seed, failure);
+        }
+    }
+}
+
+/// state of a checked projection
+struct Run<S: Source, V: Mirror + ?Sized + 'static>
+where
+    V::Msg: Clone,
+{
+    src: S,
+    port: OuterViewPort<V>,
+    mirror: Arc<RwLock<V::Observer>>,
+}
+
+impl<S: Source, V: Mirror + ?Sized + 'static> Run<S, V>
+where
+    V::Msg: Clone,
+{
+    fn new<F: Fn(&OuterViewPort<S::View>) -> OuterViewPort<V>>(build: &F) -> Self {
+        let src = S::new();
+        let port = build(&src.port());
+        let mirror = Arc::new(RwLock::new(V::Observer::default()));
+        port.add_observer(mirror.clone());
+        Run { src, port, mirror }
+    }
+
+    /// update the projection and compare it with a fresh one
+    #[allow(clippy::type_complexity)]
+    fn step<F: Fn(&OuterViewPort<S::View>) -> OuterViewPort<V>>(
+        &self,
+        build: &F,
+    ) -> Result<(), (Result<V::Contents, String>, V::Contents, V::Contents)> {
+        self.port.0.update();
+
+        let copy = self.src.copy();
+        let fresh = build(&copy.port());
+        fresh.0.update();
+
+        let expected = V::evaluate(&fresh.get_view().unwrap());
+        let live = V::evaluate(&self.port.get_view().unwrap());
+        let mirrored = V::mirrored(&self.mirror.read().unwrap());
+
+        if live == expected && mirrored.as_ref() == Ok(&expected) {
+            Ok(())
+        } else {
+            Err((mirrored, live, expected))
+        }
+    }
+}
+
+/// replay `batches` on a new source, returning the first divergence
+fn replay<S, V, F>(batches: &[Vec<S::Op>], build: &F) -> Option<Failure<S::Op, V::Contents>>
+where
+    S: Source,
+    V: Mirror + ?Sized + 'static,
+    V::Msg: Clone,
+    F: Fn(&OuterViewPort<S::View>) -> OuterViewPort<V>,
+{
+    let mut run = Run::<S, V>::new(build);
+    for (n, batch) in batches.iter().enumerate() {
+        for op in batch {
+            run.src.apply(op);
+        }
+        if let Err((mirrored, live, expected)) = run.step(build) {
+            return Some(Failure { batches: batches[..=n].to_vec(), mirrored, live, expected });
+        }
+    }
+    None
+}
+
+/// remove batches and operations as long as the projection still diverges
+fn shrink<S, V, F>(batches: Vec<Vec<S::Op>>, build: &F) -> Failure<S::Op, V::Contents>
+where
+    S: Source,
+    V: Mirror + ?Sized + 'static,
+    V::Msg: Clone,
+    F: Fn(&OuterViewPort<S::View>) -> OuterViewPort<V>,
+{
+    let mut failure = replay::<S, V, F>(&batches, build).expect("failure is not reproducible");
+
+    loop {
+        let batches = &failure.batches;
+        let without_batch = (0..batches.len()).rev().map(|i| {
+            let mut candidate = batches.clone();
+            candidate.remove(i);
+            candidate
+        });
+        let without_op = (0..batches.len()).rev()
+            .flat_map(|i| (0..batches[i].len()).rev().map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let mut candidate = batches.clone();
+                candidate[i].remove(j);
+                candidate
+            });
+
+        let smaller = without_batch.chain(without_op)
+            .find_map(|candidate| replay::<S, V, F>(&candidate, build));
+        match smaller {
+            Some(smaller) => failure = smaller,
+            None => return failure,
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::vec::*,
+        projection::decorate_sequence::{Separate, Wrap},
+        testing::*,
+        view::{ViewPort, sequence::SequenceView},
+    };
+
+    fn config(seed: u64) -> Config {
+        Config { seed, ..Config::default() }
+    }
+
+    fn digit(rng: &mut Rng) -> u8 {
+        rng.below(10) as u8
+    }
+
+    #[test]
+    fn check_sequence_projections() {
+        for seed in 0..4 {
+            check(&config(seed), vec_ops(digit), |port: &OuterViewPort<_>| {
+                port.to_sequence().filter(|x| x % 3 != 0)
+            });
+            check(&config(seed), vec_ops(digit), |port: &OuterViewPort<_>| {
+                port.to_sequence().map(|x| x * 2).sort_by_key(|x| *x)
+            });
+            check(&config(seed), vec_ops(digit), |port: &OuterViewPort<_>| {
+                port.to_sequence().wrap(10, 11).separate(12)
+            });
+            check(&config(seed), vec_ops(digit), |port: &OuterViewPort<_>| {
+                port.to_sequence()
+                    .map(|x| VecBuffer::with_data(vec![*x; *x as usize % 3]).get_port().to_sequence())
+                    .flatten()
+            });
+        }
+    }
+
+    #[test]
+    fn check_list_projections() {
+        for seed in 0..4 {
+            check(&config(seed), vec_ops(digit), |port: &OuterViewPort<_>| {
+                port.to_list().filter(|x| x % 2 == 0).enumerate()
+            });
+            check(&config(seed), vec_ops(digit), |port: &OuterViewPort<_>| {
+                port.to_list().rev().skip(2)
+            });
+        }
+    }
+
+    #[test]
+    fn check_index_projections() {
+        for seed in 0..4 {
+            check(&config(seed), index_ops(|rng| rng.below(8), digit), |port: &OuterViewPort<_>| {
+                port.map_item(|key, x| *key as u8 + *x)
+            });
+        }
+    }
+
+    #[test]
+    fn shrink_to_minimal() {
+        // never notifies its observers
+        let build = |port: &OuterViewPort<RwLock<Vec<u8>>>| {
+            let out = ViewPort::<dyn SequenceView<Item = u8>>::new();
+            out.set_view(port.to_sequence().get_view());
+            out.into_outer()
+        };
+
+        let batches = vec![
+            vec![VecOp::Update(0, 1)],
+            vec![VecOp::Update(3, 1), VecOp::Push(5), VecOp::Push(6)],
+            vec![VecOp::Clear],
+        ];
+        let failure = shrink::<VecBuffer<u8>, _, _>(batches, &build);
+        assert_eq!(failure.batches.len(), 1);
+        assert_eq!(failure.batches[0].len(), 1);
+        assert_eq!(failure.expected, vec![5]);
+        assert_eq!(failure.mirrored, Ok(vec![]));
+    }
+}
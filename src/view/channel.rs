@@ -1,5 +1,5 @@
 use {
-    crate::view::{View, Observer},
+    crate::view::{scheduler, View, Observer},
     async_std::stream::Stream,
     core::{
         pin::Pin,
@@ -94,6 +94,7 @@ where
         }
 
         state.send_buf.as_mut().unwrap().channel_insert(msg);
+        scheduler::mark_dirty();
 
         if let Some(waker) = state.waker.take() {
             waker.wake();
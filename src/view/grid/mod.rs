@@ -1,5 +1,5 @@
 use {
-    crate::view::index::{IndexArea, IndexView},
+    crate::view::index::{IndexArea, IndexView, RangeKeys},
     cgmath::Point2,
     std::{
         cmp::{max, min},
@@ -18,6 +18,12 @@ pub use window_iterator::GridWindowIterator;
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
+impl RangeKeys for Point2<i16> {
+    fn range_keys(range: &RangeInclusive<Point2<i16>>) -> Option<Vec<Point2<i16>>> {
+        Some(GridWindowIterator::from(range.clone()).collect())
+    }
+}
+
 impl IndexArea<Point2<i16>> {
     // todo: this is not perfect (e.g. diagonals are inefficient)
     pub fn iter(&self) -> GridWindowIterator {
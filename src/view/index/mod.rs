@@ -1,5 +1,6 @@
 use {
-    crate::view::View,
+    crate::view::{View, ViewSnapshot},
+    serde::{Deserialize, Serialize},
     std::sync::RwLock,
     std::{
         ops::{Deref, RangeInclusive},
@@ -9,7 +10,7 @@ use {
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum IndexArea<Key> {
     Empty,
     Full,
@@ -29,6 +30,82 @@ impl<Key> IndexArea<Key> {
     }
 }
 
+impl<Key: Clone> IndexArea<Key> {
+    /// all keys of the area, or `None` if it can not be enumerated
+    pub fn keys(&self) -> Option<Vec<Key>> {
+        match self {
+            IndexArea::Empty => Some(vec![]),
+            IndexArea::Full => None,
+            IndexArea::Set(keys) => Some(keys.clone()),
+            IndexArea::Range(r) => Key::range_keys(r),
+        }
+    }
+}
+
+/// Keys whose ranges can be enumerated, like the cells of a grid
+pub trait RangeKeys: Sized {
+    /// all keys in `range`, or `None` if they can not be enumerated
+    fn range_keys(range: &RangeInclusive<Self>) -> Option<Vec<Self>>;
+}
+
+impl<Key> RangeKeys for Key {
+    default fn range_keys(_range: &RangeInclusive<Key>) -> Option<Vec<Key>> {
+        None
+    }
+}
+
+impl<Key: Clone + PartialOrd> IndexArea<Key> {
+    /// contains all keys of both areas,
+    /// ranges are widened to cover the other area
+    pub fn merge(&self, other: &Self) -> Self {
+        match (self, other) {
+            (IndexArea::Empty, a) | (a, IndexArea::Empty) => a.clone(),
+            (IndexArea::Full, _) | (_, IndexArea::Full) => IndexArea::Full,
+            (IndexArea::Set(a), IndexArea::Set(b)) => {
+                let mut keys = a.clone();
+                keys.extend(b.iter().filter(|key| !a.contains(key)).cloned());
+                IndexArea::Set(keys)
+            }
+            (IndexArea::Range(r), IndexArea::Set(keys)) | (IndexArea::Set(keys), IndexArea::Range(r)) => {
+                let (mut start, mut end) = (r.start(), r.end());
+                for key in keys.iter() {
+                    if key < start { start = key; }
+                    if key > end { end = key; }
+                }
+                IndexArea::Range(start.clone()..=end.clone())
+            }
+            (IndexArea::Range(a), IndexArea::Range(b)) => {
+                let start = if b.start() < a.start() { b.start() } else { a.start() };
+                let end = if b.end() > a.end() { b.end() } else { a.end() };
+                IndexArea::Range(start.clone()..=end.clone())
+            }
+        }
+    }
+
+    /// contains the keys which are in both areas
+    pub fn intersection(&self, other: &Self) -> Self {
+        match (self, other) {
+            (IndexArea::Empty, _) | (_, IndexArea::Empty) => IndexArea::Empty,
+            (IndexArea::Full, a) | (a, IndexArea::Full) => a.clone(),
+            (IndexArea::Set(a), IndexArea::Set(b)) => {
+                IndexArea::Set(a.iter().filter(|key| b.contains(key)).cloned().collect())
+            }
+            (IndexArea::Range(r), IndexArea::Set(keys)) | (IndexArea::Set(keys), IndexArea::Range(r)) => {
+                IndexArea::Set(keys.iter().filter(|key| r.contains(key)).cloned().collect())
+            }
+            (IndexArea::Range(a), IndexArea::Range(b)) => {
+                let start = if b.start() > a.start() { b.start() } else { a.start() };
+                let end = if b.end() < a.end() { b.end() } else { a.end() };
+                if start <= end {
+                    IndexArea::Range(start.clone()..=end.clone())
+                } else {
+                    IndexArea::Empty
+                }
+            }
+        }
+    }
+}
+
 pub trait IndexView<Key>: View<Msg = IndexArea<Key>>
 where
     Key: Send + Sync,
@@ -96,6 +173,15 @@ where
     }
 }
 
+impl<Key, Item> ViewSnapshot for dyn IndexView<Key, Item = Item>
+where
+    Key: Send + Sync,
+{
+    fn snapshot(old: Option<Arc<Self>>, new: Option<Arc<Self>>) -> Vec<IndexArea<Key>> {
+        vec![ old.area(), new.area() ]
+    }
+}
+
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 /*
 pub trait ImplIndexView : Send + Sync {
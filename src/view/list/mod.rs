@@ -1,8 +1,11 @@
-use crate::view::View;
+use {
+    crate::view::{View, ViewSnapshot},
+    serde::{Deserialize, Serialize},
+};
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum ListDiff<T>
 where T: Clone + Send + Sync + 'static
 {
@@ -100,3 +103,20 @@ impl<Item: Clone + Send + Sync + 'static, V: ListView<Item>> ListView<Item> for
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
+impl<T> ViewSnapshot for dyn ListView<T>
+where T: Clone + Send + Sync + 'static
+{
+    fn snapshot(_old: Option<Arc<Self>>, new: Option<Arc<Self>>) -> Vec<ListDiff<T>> {
+        let mut diffs = vec![ ListDiff::Clear ];
+        if let Some(view) = new.as_ref() {
+            diffs.extend(
+                view.iter()
+                    .enumerate()
+                    .map(|(idx, val)| ListDiff::Insert { idx, val })
+            );
+        }
+        diffs
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
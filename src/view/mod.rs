@@ -30,11 +30,21 @@ impl<V: View> View for Option<V> {
 pub mod channel;
 pub mod observer;
 pub mod port;
+pub mod scheduler;
+pub mod transaction;
+pub mod updater;
+pub mod stream;
+pub mod registry;
 
 pub use {
     channel::{queue_channel, set_channel, singleton_channel, ChannelReceiver, ChannelSender},
     observer::{NotifyFnObserver, Observer, ObserverBroadcast, ObserverExt, ResetFnObserver},
-    port::{AnyInnerViewPort, AnyOuterViewPort, AnyViewPort, InnerViewPort, OuterViewPort, ViewPort}
+    port::{AnyInnerViewPort, AnyOuterViewPort, AnyViewPort, InnerViewPort, OuterViewPort, ViewPort},
+    scheduler::UpdateScheduler,
+    transaction::Transaction,
+    updater::Updater,
+    stream::{PortStream, ViewSnapshot},
+    registry::{PortKey, PortRegistry},
 };
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
@@ -15,6 +15,9 @@ use {
 pub trait Observer<V: View + ?Sized>: Send + Sync {
     fn reset(&mut self, _view: Option<Arc<V>>) {}
     fn notify(&mut self, msg: &V::Msg);
+
+    /// called after all messages of one update were notified
+    fn end_update(&mut self) {}
 }
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
@@ -27,6 +30,10 @@ impl<V: View + ?Sized, O: Observer<V>> Observer<V> for Arc<RwLock<O>> {
     fn notify(&mut self, msg: &V::Msg) {
         self.write().unwrap().notify(msg);
     }
+
+    fn end_update(&mut self) {
+        self.write().unwrap().end_update();
+    }
 }
 
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
@@ -55,6 +62,7 @@ where
     rx: ChannelReceiver<Vec<V::Msg>>,
     tx: ChannelSender<Vec<V::Msg>>,
     observers: Vec<Weak<RwLock<dyn Observer<V>>>>,
+    wake_signals: Vec<Weak<ChannelSender<Option<()>>>>,
 }
 
 impl<V: View + ?Sized> ObserverBroadcast<V>
@@ -67,6 +75,16 @@ where
             rx,
             tx,
             observers: Vec::new(),
+            wake_signals: Vec::new(),
+        }
+    }
+
+    /// `signal` will be sent a message whenever new notifications
+    /// are queued in this broadcast
+    pub fn add_wake_signal(&mut self, signal: &Arc<ChannelSender<Option<()>>>) {
+        self.wake_signals.retain(|s| s.strong_count() > 0);
+        if !self.wake_signals.iter().any(|s| std::ptr::eq(s.as_ptr(), Arc::as_ptr(signal))) {
+            self.wake_signals.push(Arc::downgrade(signal));
         }
     }
 
@@ -75,6 +93,11 @@ where
         self.observers.push(obs);
     }
 
+    pub fn remove_observer(&mut self, obs: &Arc<RwLock<dyn Observer<V>>>) {
+        let ptr = Arc::as_ptr(obs) as *const ();
+        self.observers.retain(|o| o.strong_count() > 0 && o.as_ptr() as *const () != ptr);
+    }
+
     fn cleanup(&mut self) {
         self.observers.retain(|o| o.strong_count() > 0);
     }
@@ -90,6 +113,9 @@ where
                     o.write().unwrap().notify(&msg);
                 }
             }
+            for o in self.iter() {
+                o.write().unwrap().end_update();
+            }
         }
     }
 }
@@ -106,6 +132,9 @@ where
 
     fn notify(&mut self, msg: &V::Msg) {
         self.tx.send(msg.clone());
+        for signal in self.wake_signals.iter().filter_map(|s| s.upgrade()) {
+            signal.send(());
+        }
     }
 }
 
@@ -1,11 +1,20 @@
 use {
-    crate::view::{NotifyFnObserver, Observer, ObserverBroadcast, ResetFnObserver, View},
+    crate::view::{
+        channel::ChannelSender,
+        scheduler::{self, UpdateGuard},
+        transaction,
+        NotifyFnObserver, Observer, ObserverBroadcast, ResetFnObserver, View,
+    },
     std::any::Any,
     std::sync::{Arc, RwLock}
 };
 
 pub trait UpdateTask: Send + Sync {
     fn update(&self);
+
+    /// register `signal` at all broadcasts this task depends on,
+    /// so it gets woken up when there is something to update
+    fn add_wake_signal(&self, _signal: &Arc<ChannelSender<Option<()>>>) {}
 }
 
                     /*\
@@ -38,6 +47,7 @@ where
     }
 
     pub fn set_view(&self, view: Option<Arc<V>>) {
+        let _guard = UpdateGuard::new();
         self.update();
         *self.view.write().unwrap() = view.clone();
         self.cast.write().unwrap().reset(view);
@@ -48,6 +58,7 @@ where
     }
 
     pub fn add_observer(&self, observer: Arc<RwLock<dyn Observer<V>>>) {
+        let _guard = UpdateGuard::new();
         self.update();
         self.cast
             .write()
@@ -119,6 +130,7 @@ where
     V::Msg: Clone + Send + Sync,
 {
     fn update(&self) {
+        let _guard = UpdateGuard::new();
         let v = {
             let t = self.update_hooks.read().unwrap();
             t.iter().cloned().collect::<Vec<_>>()
@@ -130,6 +142,19 @@ where
 
         self.cast.read().unwrap().update();
     }
+
+    fn add_wake_signal(&self, signal: &Arc<ChannelSender<Option<()>>>) {
+        let v = {
+            let t = self.update_hooks.read().unwrap();
+            t.iter().cloned().collect::<Vec<_>>()
+        };
+
+        for hook in v {
+            hook.add_wake_signal(signal);
+        }
+
+        self.cast.write().unwrap().add_wake_signal(signal);
+    }
 }
 
 impl<V: View + ?Sized> Clone for ViewPort<V>
@@ -173,8 +198,21 @@ where
         self.0.view.read().unwrap().clone()
     }
 
+}
+
+impl<V: View + ?Sized + 'static> InnerViewPort<V>
+where
+    V::Msg: Clone,
+{
     pub fn notify(&self, msg: &V::Msg) {
+        if transaction::is_open() {
+            let (cast, msg) = (self.0.cast.clone(), msg.clone());
+            transaction::defer(move || cast.write().unwrap().notify(&msg));
+            return;
+        }
+
         self.0.cast.write().unwrap().notify(msg);
+        scheduler::schedule();
     }
 }
 
@@ -245,26 +283,6 @@ where V::Msg: Clone
     }
 }
 
-/*
-impl<V: View + ?Sized + 'static> OuterViewPort<V>
-where V::Msg: Clone {
-    pub fn into_stream<Data>(
-        self,
-        reset: impl Fn(Option<Arc<V>>, ChannelSender<Data>) + Send + Sync + 'static
-    ) -> ChannelReceiver<Data>
-    where Data: ChannelData<Item = V::Msg> + 'static,
-          Data::IntoIter: Send + Sync + 'static
-    {
-        let (s, r) = crate::core::channel::channel::<Data>();
-        self.add_observer(Arc::new(s.clone()));
-        self.add_reset_fn(
-            move |view| { reset(view, s.clone()); }
-        );
-        r
-    }
-}
-*/
-
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 
 #[derive(Clone)]
@@ -0,0 +1,254 @@
+use {
+    crate::view::{AnyOuterViewPort, AnyViewPort, Observer, OuterViewPort, View, ViewPort},
+    std::{
+        any::{type_name, TypeId},
+        collections::HashMap,
+        fmt,
+        sync::{Arc, RwLock},
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PortKey {
+    Name(String),
+    Type(TypeId, &'static str),
+}
+
+impl PortKey {
+    /// key of the default port for views of type `V`
+    pub fn of<V: ?Sized + 'static>() -> Self {
+        PortKey::Type(TypeId::of::<V>(), type_name::<V>())
+    }
+}
+
+impl From<&str> for PortKey {
+    fn from(name: &str) -> Self {
+        PortKey::Name(name.into())
+    }
+}
+
+impl From<String> for PortKey {
+    fn from(name: String) -> Self {
+        PortKey::Name(name)
+    }
+}
+
+impl fmt::Display for PortKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PortKey::Name(name) => write!(f, "'{}'", name),
+            PortKey::Type(_, name) => write!(f, "<{}>", name),
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[derive(Debug)]
+pub enum RegistryError {
+    NotFound(PortKey),
+    TypeMismatch {
+        key: PortKey,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistryError::NotFound(key) => write!(f, "no port registered as {}", key),
+            RegistryError::TypeMismatch { key, expected, found } => write!(
+                f,
+                "port {} has view type `{}`, but `{}` was requested",
+                key, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+struct RegistryEntry {
+    /// indirection which is handed out to all users of the entry,
+    /// so the source can be replaced later on
+    port: AnyViewPort,
+    source: AnyOuterViewPort,
+    type_name: &'static str,
+}
+
+/// Shared directory of ports, keyed by name or view type.
+#[derive(Clone, Default)]
+pub struct PortRegistry {
+    entries: Arc<RwLock<HashMap<PortKey, RegistryEntry>>>,
+}
+
+impl PortRegistry {
+    pub fn new() -> Self {
+        PortRegistry::default()
+    }
+
+    /// register `port` under `key`, replacing any previous entry
+    /// without re-pointing its users
+    pub fn insert<V>(&self, key: impl Into<PortKey>, port: OuterViewPort<V>)
+    where
+        V: View + ?Sized + 'static,
+        V::Msg: Clone,
+    {
+        let indirection = ViewPort::<V>::new();
+        indirection.attach_to(port.clone());
+
+        self.entries.write().unwrap().insert(
+            key.into(),
+            RegistryEntry {
+                port: indirection.into(),
+                source: port.into(),
+                type_name: type_name::<V>(),
+            },
+        );
+    }
+
+    pub fn get<V>(&self, key: impl Into<PortKey>) -> Result<OuterViewPort<V>, RegistryError>
+    where
+        V: View + ?Sized + 'static,
+        V::Msg: Clone,
+    {
+        Ok(self.get_indirection::<V>(&key.into())?.into_outer())
+    }
+
+    pub fn get_any(&self, key: impl Into<PortKey>) -> Option<AnyOuterViewPort> {
+        let entries = self.entries.read().unwrap();
+        entries
+            .get(&key.into())
+            .map(|entry| AnyOuterViewPort(entry.port.clone()))
+    }
+
+    /// name of the view type registered under `key`
+    pub fn type_name(&self, key: impl Into<PortKey>) -> Option<&'static str> {
+        let entries = self.entries.read().unwrap();
+        entries.get(&key.into()).map(|entry| entry.type_name)
+    }
+
+    pub fn contains(&self, key: impl Into<PortKey>) -> bool {
+        self.entries.read().unwrap().contains_key(&key.into())
+    }
+
+    pub fn remove(&self, key: impl Into<PortKey>) -> Option<AnyOuterViewPort> {
+        let entry = self.entries.write().unwrap().remove(&key.into())?;
+        Some(AnyOuterViewPort(entry.port))
+    }
+
+    /// make the entry `key` refer to `port`.
+    /// All ports previously returned by `get()` are attached to the new source.
+    pub fn replace<V>(&self, key: impl Into<PortKey>, port: OuterViewPort<V>) -> Result<(), RegistryError>
+    where
+        V: View + ?Sized + 'static,
+        V::Msg: Clone,
+    {
+        let key = key.into();
+        let indirection = self.get_indirection::<V>(&key)?;
+
+        let old_source = {
+            let mut entries = self.entries.write().unwrap();
+            let entry = entries
+                .get_mut(&key)
+                .ok_or_else(|| RegistryError::NotFound(key.clone()))?;
+            std::mem::replace(&mut entry.source, port.clone().into())
+        };
+
+        if let Ok(old_source) = old_source.downcast::<V>() {
+            let cast: Arc<RwLock<dyn Observer<V>>> = indirection.get_cast();
+            old_source.0.get_cast().write().unwrap().remove_observer(&cast);
+        }
+
+        indirection.attach_to(port);
+        Ok(())
+    }
+
+    fn get_indirection<V>(&self, key: &PortKey) -> Result<ViewPort<V>, RegistryError>
+    where
+        V: View + ?Sized + 'static,
+        V::Msg: Clone,
+    {
+        let entries = self.entries.read().unwrap();
+        let entry = entries
+            .get(key)
+            .ok_or_else(|| RegistryError::NotFound(key.clone()))?;
+
+        entry
+            .port
+            .clone()
+            .downcast::<V>()
+            .map_err(|_| RegistryError::TypeMismatch {
+                key: key.clone(),
+                expected: type_name::<V>(),
+                found: entry.type_name,
+            })
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::{singleton::*, vec::*},
+        view::{port::UpdateTask, registry::*, sequence::*, singleton::*},
+    };
+
+    #[test]
+    fn registry_lookup() {
+        let buf = VecBuffer::<char>::with_data(vec!['a', 'b']);
+        let registry = PortRegistry::new();
+        registry.insert("text", buf.get_port().to_sequence());
+
+        let port = registry.get::<dyn SequenceView<Item = char>>("text").unwrap();
+        assert_eq!(port.get_view().get(&1), Some('b'));
+
+        match registry.get::<dyn SequenceView<Item = u32>>("text") {
+            Err(RegistryError::TypeMismatch { expected, found, .. }) => {
+                assert!(expected.contains("u32"));
+                assert!(found.contains("char"));
+            }
+            _ => panic!("expected type mismatch"),
+        }
+        assert!(matches!(
+            registry.get::<dyn SequenceView<Item = char>>("other"),
+            Err(RegistryError::NotFound(_))
+        ));
+
+        let sgl = SingletonBuffer::new(3);
+        registry.insert(PortKey::of::<dyn SingletonView<Item = i32>>(), sgl.get_port());
+        let port = registry
+            .get::<dyn SingletonView<Item = i32>>(PortKey::of::<dyn SingletonView<Item = i32>>())
+            .unwrap();
+        assert_eq!(port.get_view().get(), 3);
+    }
+
+    #[test]
+    fn registry_replace() {
+        let mut buf1 = VecBuffer::<char>::with_data(vec!['a']);
+        let buf2 = VecBuffer::<char>::with_data(vec!['x', 'y']);
+
+        let registry = PortRegistry::new();
+        registry.insert("text", buf1.get_port().to_list());
+
+        let dst = VecBuffer::<char>::new();
+        let _keepalive = dst.attach_to(registry.get("text").unwrap());
+        assert_eq!(dst.len(), 1);
+
+        registry.replace("text", buf2.get_port().to_list()).unwrap();
+        dst.get_port().0.update();
+        assert_eq!(dst.len(), 2);
+        assert_eq!(dst.get(1), 'y');
+
+        // the old source is detached
+        buf1.push('b');
+        dst.get_port().0.update();
+        assert_eq!(dst.len(), 2);
+    }
+}
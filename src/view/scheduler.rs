@@ -0,0 +1,250 @@
+use {
+    crate::view::{port::UpdateTask, OuterViewPort, View},
+    std::{
+        cell::{Cell, RefCell},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// number of flushes after which `run_until_stable()` gives up,
+/// since a feedback cycle between ports would never become stable
+pub const MAX_ROUNDS: usize = 1024;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<UpdateScheduler>>> = const { RefCell::new(None) };
+    static UPDATE_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+                    /*\
+<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+              Update Scheduler
+<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+                    \*/
+
+/// Drives the propagation of updates through a set of registered ports.
+///
+/// Updating a port first updates all of its update-hooks,
+/// i.e. every `ObserverBroadcast` and `ProjectionArg` it depends on,
+/// so flushing the registered ports drains all pending messages
+/// from the sources towards the sinks.
+///
+/// When entered on a thread (see `enter()`), every buffer mutation
+/// on this thread is followed by `run_until_stable()` automatically.
+pub struct UpdateScheduler {
+    /// tasks along with the address identifying them for `unregister()`
+    tasks: Mutex<Vec<(usize, Arc<dyn UpdateTask>)>>,
+    dirty: AtomicBool,
+}
+
+impl UpdateScheduler {
+    pub fn new() -> Arc<Self> {
+        Arc::new(UpdateScheduler {
+            tasks: Mutex::new(Vec::new()),
+            dirty: AtomicBool::new(false),
+        })
+    }
+
+    /// returns the scheduler which is currently entered on this thread
+    pub fn current() -> Option<Arc<Self>> {
+        CURRENT.with(|c| c.borrow().clone())
+    }
+
+    /// make this scheduler the current one of this thread
+    /// until the returned scope is dropped
+    pub fn enter(self: &Arc<Self>) -> SchedulerScope {
+        SchedulerScope {
+            prev: CURRENT.with(|c| c.borrow_mut().replace(self.clone())),
+        }
+    }
+
+    pub fn register<V: View + ?Sized + 'static>(&self, port: &OuterViewPort<V>)
+    where
+        V::Msg: Clone,
+    {
+        let key = port_key(port);
+        self.tasks.lock().unwrap().push((key, Arc::new(port.0.clone())));
+    }
+
+    /// stop updating `port`, which was registered before
+    pub fn unregister<V: View + ?Sized + 'static>(&self, port: &OuterViewPort<V>)
+    where
+        V::Msg: Clone,
+    {
+        let key = port_key(port);
+        self.tasks.lock().unwrap().retain(|(k, _)| *k != key);
+    }
+
+    pub fn add_task(&self, task: Arc<dyn UpdateTask>) {
+        let key = Arc::as_ptr(&task) as *const () as usize;
+        self.tasks.lock().unwrap().push((key, task));
+    }
+
+    pub fn remove_task(&self, task: &Arc<dyn UpdateTask>) {
+        let key = Arc::as_ptr(task) as *const () as usize;
+        self.tasks.lock().unwrap().retain(|(k, _)| *k != key);
+    }
+
+    /// update all registered ports once
+    pub fn flush(&self) {
+        let tasks: Vec<_> = self.tasks.lock().unwrap().iter().map(|(_, t)| t.clone()).collect();
+
+        let _guard = UpdateGuard::new();
+        for task in tasks {
+            task.update();
+        }
+    }
+
+    /// flush until no more messages are sent, e.g. because a projection
+    /// created new projection-args or a buffer is attached to a port.
+    /// panics after `MAX_ROUNDS` flushes
+    pub fn run_until_stable(self: &Arc<Self>) {
+        let _scope = self.enter();
+        for _ in 0..MAX_ROUNDS {
+            self.dirty.store(false, Ordering::SeqCst);
+            self.flush();
+            if !self.dirty.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+        panic!("updates did not stabilize after {} rounds, ports form a feedback cycle", MAX_ROUNDS);
+    }
+}
+
+/// all clones of a port share the same broadcast
+fn port_key<V: View + ?Sized + 'static>(port: &OuterViewPort<V>) -> usize
+where
+    V::Msg: Clone,
+{
+    Arc::as_ptr(&port.0.get_cast()) as *const () as usize
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub struct SchedulerScope {
+    prev: Option<Arc<UpdateScheduler>>,
+}
+
+impl Drop for SchedulerScope {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        CURRENT.with(|c| *c.borrow_mut() = prev);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Marks the current thread as being inside of an update or reset,
+/// where observers may hold locks and a flush must not be started.
+pub(crate) struct UpdateGuard;
+
+impl UpdateGuard {
+    pub(crate) fn new() -> Self {
+        UPDATE_DEPTH.with(|d| d.set(d.get() + 1));
+        UpdateGuard
+    }
+}
+
+impl Drop for UpdateGuard {
+    fn drop(&mut self) {
+        UPDATE_DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+/// a message was queued somewhere, so another flush is required
+pub(crate) fn mark_dirty() {
+    CURRENT.with(|c| {
+        if let Some(scheduler) = c.borrow().as_ref() {
+            scheduler.dirty.store(true, Ordering::SeqCst);
+        }
+    });
+}
+
+/// a buffer was modified, propagate the change if possible
+pub(crate) fn schedule() {
+    if UPDATE_DEPTH.with(|d| d.get()) == 0 {
+        if let Some(scheduler) = UpdateScheduler::current() {
+            scheduler.run_until_stable();
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            buffer::{singleton::*, vec::*},
+            view::{scheduler::*, sequence::*},
+        },
+        std::sync::atomic::AtomicUsize,
+    };
+
+    #[test]
+    fn scheduler_auto_update() {
+        let scheduler = UpdateScheduler::new();
+        let _scope = scheduler.enter();
+
+        let mut src = VecBuffer::<i32>::new();
+        let dst = VecBuffer::new();
+        let _keepalive = dst.attach_to(src.get_port().to_list().map(|x| x * 2));
+        scheduler.register(&dst.get_port());
+
+        src.push(1);
+        src.push(2);
+        assert_eq!(dst.len(), 2);
+        assert_eq!(dst.get(1), 4);
+
+        src.remove(0);
+        assert_eq!(dst.len(), 1);
+        assert_eq!(dst.get(0), 4);
+    }
+
+    #[test]
+    fn scheduler_flush() {
+        let scheduler = UpdateScheduler::new();
+
+        let mut src = SingletonBuffer::new(1);
+        let mapped = src.get_port().map(|x| x + 1).to_sequence();
+        let count = Arc::new(AtomicUsize::new(0));
+        let _obs = mapped.add_notify_fn({
+            let count = count.clone();
+            move |_| { count.fetch_add(1, Ordering::SeqCst); }
+        });
+        scheduler.register(&mapped);
+
+        let n = count.load(Ordering::SeqCst);
+        src.set(2);
+        assert_eq!(count.load(Ordering::SeqCst), n);
+
+        scheduler.run_until_stable();
+        assert_eq!(count.load(Ordering::SeqCst), n + 1);
+        assert_eq!(mapped.get_view().get(&0), Some(3));
+
+        scheduler.unregister(&mapped);
+        src.set(4);
+        scheduler.run_until_stable();
+        assert_eq!(count.load(Ordering::SeqCst), n + 1);
+    }
+
+    struct Cycle;
+
+    impl UpdateTask for Cycle {
+        fn update(&self) {
+            mark_dirty();
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "did not stabilize")]
+    fn scheduler_cycle() {
+        let scheduler = UpdateScheduler::new();
+        scheduler.add_task(Arc::new(Cycle));
+        scheduler.run_until_stable();
+    }
+}
@@ -1,7 +1,204 @@
 
-use crate::view::View;
+use {
+    crate::view::{channel::ChannelData, View, ViewSnapshot},
+    serde::{Deserialize, Serialize},
+    std::{cmp::{max, min}, ops::Range},
+};
 
-pub trait SequenceView: View<Msg = usize> {
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Change of a sequence.
+/// The length of a sequence only changes with `Shift`,
+/// so observers can keep track of it without querying the view.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SeqDiff {
+    /// the items at all indices of the range changed
+    Range(Range<usize>),
+
+    /// `by` new items were inserted at `at` (`by > 0`)
+    /// or `-by` items were removed at `at` (`by < 0`),
+    /// all following items are moved but unchanged
+    Shift { at: usize, by: isize },
+}
+
+impl SeqDiff {
+    /// messages to replace a sequence of `old_len` items by `new_len` other items
+    pub fn replace(old_len: usize, new_len: usize) -> Vec<SeqDiff> {
+        let mut diffs = Vec::new();
+        let common = min(old_len, new_len);
+        if common > 0 {
+            diffs.push(SeqDiff::Range(0..common));
+        }
+        if old_len != new_len {
+            diffs.push(SeqDiff::Shift {
+                at: common,
+                by: new_len as isize - old_len as isize,
+            });
+        }
+        diffs
+    }
+
+    /// all indices whose item may have changed,
+    /// given the length of the sequence after the change
+    pub fn range(&self, new_len: usize) -> Range<usize> {
+        match self {
+            SeqDiff::Range(r) => r.clone(),
+            SeqDiff::Shift { at, by } => *at..max(new_len, (new_len as isize - by) as usize),
+        }
+    }
+
+    /// length of the sequence after the change
+    pub fn apply_len(&self, len: usize) -> usize {
+        match self {
+            SeqDiff::Range(_) => len,
+            SeqDiff::Shift { by, .. } => (len as isize + by) as usize,
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Messages of one update, merged into an equivalent list of messages
+/// which go through the sequence from front to back.
+///
+/// When the messages are processed, the view already contains all
+/// changes of the update, so the item at an index might have been
+/// moved by a later message. In the merged list, every index refers to
+/// the final position of its item, so observers can read the view
+/// at the notified indices.
+#[derive(Clone, Debug, Default)]
+pub struct SeqDiffBatch {
+    /// segments of the new sequence
+    segments: Vec<Segment>,
+
+    /// the old items from `tail` on follow the segments unchanged
+    tail: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Segment {
+    Old { start: usize, len: usize, changed: bool },
+    New(usize),
+}
+
+impl Segment {
+    fn len(&self) -> usize {
+        match self {
+            Segment::Old { len, .. } => *len,
+            Segment::New(len) => *len,
+        }
+    }
+}
+
+impl SeqDiffBatch {
+    pub fn push(&mut self, diff: SeqDiff) {
+        match diff {
+            SeqDiff::Range(r) => {
+                let i = self.split(r.start);
+                let j = self.split(max(r.start, r.end));
+                for segment in self.segments[i..j].iter_mut() {
+                    if let Segment::Old { changed, .. } = segment {
+                        *changed = true;
+                    }
+                }
+            }
+            SeqDiff::Shift { at, by } if by > 0 => {
+                let i = self.split(at);
+                self.segments.insert(i, Segment::New(by as usize));
+            }
+            SeqDiff::Shift { at, by } => {
+                let i = self.split(at);
+                let j = self.split(at + by.unsigned_abs());
+                self.segments.drain(i..j);
+            }
+        }
+    }
+
+    /// index of the segment starting at `pos`
+    fn split(&mut self, pos: usize) -> usize {
+        let mut offset = 0;
+        for i in 0..self.segments.len() {
+            let len = self.segments[i].len();
+            if pos == offset {
+                return i;
+            }
+            if pos < offset + len {
+                let k = pos - offset;
+                let rest = match &mut self.segments[i] {
+                    Segment::Old { start, len, changed } => {
+                        let rest = Segment::Old { start: *start + k, len: *len - k, changed: *changed };
+                        *len = k;
+                        rest
+                    }
+                    Segment::New(len) => {
+                        let rest = Segment::New(*len - k);
+                        *len = k;
+                        rest
+                    }
+                };
+                self.segments.insert(i + 1, rest);
+                return i + 1;
+            }
+            offset += len;
+        }
+
+        if pos > offset {
+            self.segments.push(Segment::Old { start: self.tail, len: pos - offset, changed: false });
+            self.tail += pos - offset;
+        }
+        self.segments.len()
+    }
+}
+
+impl IntoIterator for SeqDiffBatch {
+    type Item = SeqDiff;
+    type IntoIter = std::vec::IntoIter<SeqDiff>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut diffs = Vec::new();
+        let mut pos = 0;
+        let mut old = 0;
+
+        let emit = |diffs: &mut Vec<SeqDiff>, diff: SeqDiff| match (diffs.last_mut(), diff) {
+            (Some(SeqDiff::Range(last)), SeqDiff::Range(r)) if last.end == r.start => last.end = r.end,
+            (_, diff) => diffs.push(diff),
+        };
+
+        for segment in self.segments {
+            match segment {
+                Segment::Old { start, len, changed } => {
+                    if start > old {
+                        emit(&mut diffs, SeqDiff::Shift { at: pos, by: -((start - old) as isize) });
+                    }
+                    if changed && len > 0 {
+                        emit(&mut diffs, SeqDiff::Range(pos..pos + len));
+                    }
+                    pos += len;
+                    old = start + len;
+                }
+                Segment::New(len) => {
+                    emit(&mut diffs, SeqDiff::Shift { at: pos, by: len as isize });
+                    pos += len;
+                }
+            }
+        }
+        if self.tail > old {
+            emit(&mut diffs, SeqDiff::Shift { at: pos, by: -((self.tail - old) as isize) });
+        }
+
+        diffs.into_iter()
+    }
+}
+
+impl ChannelData for SeqDiffBatch {
+    fn channel_insert(&mut self, diff: SeqDiff) {
+        self.push(diff);
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+pub trait SequenceView: View<Msg = SeqDiff> {
     type Item;
 
     fn get(&self, idx: &usize) -> Option<Self::Item>;
@@ -91,3 +288,46 @@ impl<V: SequenceView> SequenceView for Option<V> {
         }
     }
 }
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<Item> ViewSnapshot for dyn SequenceView<Item = Item> {
+    fn snapshot(old: Option<Arc<Self>>, new: Option<Arc<Self>>) -> Vec<SeqDiff> {
+        SeqDiff::replace(old.len().unwrap_or(0), new.len().unwrap_or(0))
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use crate::view::sequence::*;
+
+    #[test]
+    fn seq_diff_batch() {
+        let mut batch = SeqDiffBatch::default();
+        batch.push(SeqDiff::Range(3..4));
+        batch.push(SeqDiff::Shift { at: 0, by: -1 });
+        batch.push(SeqDiff::Shift { at: 50, by: 1 });
+        batch.push(SeqDiff::Range(50..51));
+        batch.push(SeqDiff::Shift { at: 100, by: 1 });
+
+        assert_eq!(
+            batch.into_iter().collect::<Vec<_>>(),
+            vec![
+                SeqDiff::Shift { at: 0, by: -1 },
+                SeqDiff::Range(2..3),
+                SeqDiff::Shift { at: 50, by: 1 },
+                SeqDiff::Shift { at: 100, by: 1 },
+            ]
+        );
+
+        let mut batch = SeqDiffBatch::default();
+        batch.push(SeqDiff::Shift { at: 2, by: 3 });
+        batch.push(SeqDiff::Shift { at: 1, by: -5 });
+        assert_eq!(
+            batch.into_iter().collect::<Vec<_>>(),
+            vec![SeqDiff::Shift { at: 1, by: -2 }]
+        );
+    }
+}
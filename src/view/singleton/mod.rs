@@ -1,5 +1,5 @@
 use {
-    crate::{view::View},
+    crate::{view::{View, ViewSnapshot}},
     std::{ops::Deref, sync::{Arc, RwLock}},
 };
 
@@ -43,6 +43,12 @@ where
     }
 }
 
+impl<Item> ViewSnapshot for dyn SingletonView<Item = Item> {
+    fn snapshot(_old: Option<Arc<Self>>, _new: Option<Arc<Self>>) -> Vec<()> {
+        vec![ () ]
+    }
+}
+
 //<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
 /*
 impl<T> OuterViewPort<dyn SingletonView<Item = T>> {
@@ -0,0 +1,187 @@
+use {
+    crate::view::{
+        channel::{channel, ChannelData, ChannelReceiver, ChannelSender},
+        Observer, OuterViewPort, Updater, View,
+    },
+    async_std::stream::Stream,
+    core::{
+        pin::Pin,
+        task::{Context, Poll},
+    },
+    std::{
+        any::Any,
+        collections::HashSet,
+        hash::Hash,
+        sync::{Arc, RwLock},
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Views which can be described completely by a sequence of diffs
+pub trait ViewSnapshot: View {
+    /// messages which transform the contents of `old` into `new`
+    fn snapshot(old: Option<Arc<Self>>, new: Option<Arc<Self>>) -> Vec<Self::Msg>;
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+struct StreamObserver<V, Data>
+where
+    V: ViewSnapshot + ?Sized,
+    Data: ChannelData<Item = V::Msg>,
+    Data::IntoIter: Send + Sync,
+{
+    view: Option<Arc<V>>,
+    tx: ChannelSender<Data>,
+}
+
+impl<V, Data> Observer<V> for StreamObserver<V, Data>
+where
+    V: ViewSnapshot + ?Sized,
+    V::Msg: Clone,
+    Data: ChannelData<Item = V::Msg>,
+    Data::IntoIter: Send + Sync,
+{
+    fn reset(&mut self, view: Option<Arc<V>>) {
+        let old_view = std::mem::replace(&mut self.view, view.clone());
+        for msg in V::snapshot(old_view, view) {
+            self.tx.send(msg);
+        }
+    }
+
+    fn notify(&mut self, msg: &V::Msg) {
+        self.tx.send(msg.clone());
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Stream of all diffs of a port, beginning with a snapshot of its current view.
+/// Keeps the port updated in the background while it is alive.
+pub struct PortStream<Data: ChannelData> {
+    rx: ChannelReceiver<Data>,
+    _observer: Arc<dyn Any + Send + Sync>,
+    _updater: Updater,
+}
+
+impl<Data: ChannelData> PortStream<Data> {
+    pub async fn recv(&self) -> Option<Data> {
+        self.rx.recv().await
+    }
+
+    pub fn try_recv(&self) -> Option<Data> {
+        self.rx.try_recv()
+    }
+}
+
+impl<Data: ChannelData> Stream for PortStream<Data> {
+    type Item = Data::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().rx).poll_next(cx)
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<V: ViewSnapshot + ?Sized + 'static> OuterViewPort<V>
+where
+    V::Msg: Clone,
+{
+    pub fn into_stream<Data>(self) -> PortStream<Data>
+    where
+        Data: ChannelData<Item = V::Msg> + 'static,
+        Data::IntoIter: Send + Sync + 'static,
+    {
+        let (tx, rx) = channel::<Data>();
+        let observer = Arc::new(RwLock::new(StreamObserver { view: None, tx }));
+        self.add_observer(observer.clone());
+
+        PortStream {
+            rx,
+            _observer: observer,
+            _updater: self.spawn_updater(),
+        }
+    }
+
+    /// stream of all diffs in order
+    pub fn into_queue_stream(self) -> PortStream<Vec<V::Msg>> {
+        self.into_stream()
+    }
+
+    /// stream of only the latest diff
+    pub fn into_singleton_stream(self) -> PortStream<Option<V::Msg>> {
+        self.into_stream()
+    }
+}
+
+impl<V: ViewSnapshot + ?Sized + 'static> OuterViewPort<V>
+where
+    V::Msg: Clone + Eq + Hash,
+{
+    /// stream of deduplicated diffs, e.g. changed indices
+    pub fn into_set_stream(self) -> PortStream<HashSet<V::Msg>> {
+        self.into_stream()
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            buffer::{singleton::*, vec::*},
+            view::{list::ListDiff, sequence::SeqDiff},
+        },
+        async_std::{
+            future::timeout,
+            stream::{Stream, StreamExt},
+        },
+        std::time::Duration,
+    };
+
+    async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+        timeout(Duration::from_secs(1), stream.next()).await.unwrap()
+    }
+
+    #[async_std::test]
+    async fn list_stream() {
+        let mut buf = VecBuffer::<char>::with_data(vec!['a']);
+        let mut stream = buf.get_port().to_list().into_queue_stream();
+
+        assert!(matches!(next(&mut stream).await, Some(ListDiff::Clear)));
+        assert!(matches!(next(&mut stream).await, Some(ListDiff::Insert { idx: 0, val: 'a' })));
+
+        buf.push('b');
+        assert!(matches!(next(&mut stream).await, Some(ListDiff::Insert { idx: 1, val: 'b' })));
+
+        buf.remove(0);
+        assert!(matches!(next(&mut stream).await, Some(ListDiff::Remove(0))));
+    }
+
+    #[async_std::test]
+    async fn sequence_set_stream() {
+        let buf = VecBuffer::<char>::with_data(vec!['a', 'b']);
+        let stream = buf.get_port().to_sequence().into_set_stream();
+
+        let diffs = timeout(Duration::from_secs(1), stream.recv()).await.unwrap().unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs.contains(&SeqDiff::Shift { at: 0, by: 2 }));
+    }
+
+    #[async_std::test]
+    async fn singleton_stream() {
+        let mut buf = SingletonBuffer::new(0);
+        let port = buf.get_port().map(|x| x * 2);
+        let stream = port.clone().into_singleton_stream();
+
+        assert_eq!(timeout(Duration::from_secs(1), stream.recv()).await, Ok(Some(Some(()))));
+
+        buf.set(1);
+        buf.set(2);
+        assert_eq!(timeout(Duration::from_secs(1), stream.recv()).await, Ok(Some(Some(()))));
+        assert_eq!(port.get_view().unwrap().get(), 4);
+    }
+}
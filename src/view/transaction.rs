@@ -0,0 +1,237 @@
+use {
+    crate::view::scheduler::{self, UpdateGuard},
+    std::{
+        cell::RefCell,
+        marker::PhantomData,
+        sync::{Arc, RwLock},
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+thread_local! {
+    /// stack of the open transactions of this thread, innermost last
+    static OPEN: RefCell<Vec<TransactionState>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Default)]
+struct TransactionState {
+    /// notifications which are held back until commit
+    deferred: Vec<Box<dyn FnOnce()>>,
+
+    /// restores the previous contents of every modified buffer,
+    /// keyed by the address of the buffer data
+    rollbacks: Vec<(usize, Box<dyn FnOnce()>)>,
+}
+
+                    /*\
+<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+                Transaction
+<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+                    \*/
+
+/// Groups the modifications of any number of buffers.
+///
+/// While a transaction is open on this thread, all notifications
+/// sent by buffers are held back, so no observer sees intermediate states.
+/// On `commit()` they are sent at once and delivered as one batch with the next update.
+/// On `abort()`, or when the transaction is dropped without commit,
+/// the modified buffers are rolled back to their previous contents.
+///
+/// Transactions can be nested, committing an inner transaction
+/// hands its modifications over to the outer one.
+/// A transaction belongs to the thread which began it
+/// and has to be closed before the transactions outside of it.
+pub struct Transaction {
+    done: bool,
+
+    /// position in the stack of open transactions
+    depth: usize,
+    not_send: PhantomData<*const ()>,
+}
+
+impl Transaction {
+    pub fn begin() -> Self {
+        let depth = OPEN.with(|open| {
+            let mut open = open.borrow_mut();
+            open.push(TransactionState::default());
+            open.len()
+        });
+        Transaction { done: false, depth, not_send: PhantomData }
+    }
+
+    pub fn commit(mut self) {
+        let state = self.pop().expect("no open transaction");
+
+        // the outermost transaction sends the notifications
+        let state = OPEN.with(|open| {
+            if let Some(outer) = open.borrow_mut().last_mut() {
+                outer.deferred.extend(state.deferred);
+                for (key, rollback) in state.rollbacks {
+                    // the outer transaction keeps its older snapshot
+                    if !outer.rollbacks.iter().any(|(k, _)| *k == key) {
+                        outer.rollbacks.push((key, rollback));
+                    }
+                }
+                None
+            } else {
+                Some(state)
+            }
+        });
+
+        if let Some(state) = state {
+            {
+                let _guard = UpdateGuard::new();
+                for notify in state.deferred {
+                    notify();
+                }
+            }
+            scheduler::schedule();
+        }
+    }
+
+    pub fn abort(mut self) {
+        self.rollback();
+    }
+
+    fn rollback(&mut self) {
+        if let Some(state) = self.pop() {
+            for (_, rollback) in state.rollbacks.into_iter().rev() {
+                rollback();
+            }
+        }
+    }
+
+    /// state of this transaction, which has to be the innermost one
+    fn pop(&mut self) -> Option<TransactionState> {
+        self.done = true;
+        OPEN.with(|open| {
+            let mut open = open.borrow_mut();
+            if open.len() != self.depth {
+                assert!(std::thread::panicking(), "transaction closed before the ones inside of it");
+                return None;
+            }
+            open.pop()
+        })
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if !self.done {
+            self.rollback();
+        }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// holds back `notify` until the open transaction is committed
+pub(crate) fn defer(notify: impl FnOnce() + 'static) {
+    OPEN.with(|open| {
+        if let Some(state) = open.borrow_mut().last_mut() {
+            state.deferred.push(Box::new(notify));
+        }
+    });
+}
+
+pub(crate) fn is_open() -> bool {
+    OPEN.with(|open| !open.borrow().is_empty())
+}
+
+/// remember the contents of `data` before it is first
+/// modified inside of the open transaction
+pub(crate) fn save<T: Clone + Send + Sync + 'static>(data: &Arc<RwLock<T>>) {
+    OPEN.with(|open| {
+        if let Some(state) = open.borrow_mut().last_mut() {
+            let key = Arc::as_ptr(data) as usize;
+            if !state.rollbacks.iter().any(|(k, _)| *k == key) {
+                let data = data.clone();
+                let snapshot = data.read().unwrap().clone();
+                state.rollbacks.push((key, Box::new(move || {
+                    *data.write().unwrap() = snapshot;
+                })));
+            }
+        }
+    });
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            buffer::{singleton::*, vec::*},
+            view::{OuterViewPort, UpdateScheduler, Transaction, list::ListView},
+        },
+        std::sync::{Arc, Mutex},
+    };
+
+    #[test]
+    fn transaction_commit() {
+        let scheduler = UpdateScheduler::new();
+        let _scope = scheduler.enter();
+
+        let mut a = VecBuffer::with_data(vec![1, 2, 3]);
+        let mut b = VecBuffer::<i32>::new();
+
+        // total number of items, as seen by the observers of both lists
+        let totals = Arc::new(Mutex::new(Vec::new()));
+        let observe = |port: OuterViewPort<dyn ListView<i32>>| {
+            let (a, b, totals) = (a.clone(), b.clone(), totals.clone());
+            scheduler.register(&port);
+            port.add_notify_fn(move |_| totals.lock().unwrap().push(a.len() + b.len()))
+        };
+        let _obs = (observe(a.get_port().to_list()), observe(b.get_port().to_list()));
+
+        // move the first item from `a` to `b`
+        let tx = Transaction::begin();
+        let x = a.get(0);
+        a.remove(0);
+        b.push(x);
+        assert!(totals.lock().unwrap().is_empty());
+        tx.commit();
+
+        assert_eq!(*totals.lock().unwrap(), vec![3, 3]);
+    }
+
+    #[test]
+    fn transaction_abort() {
+        let mut a = VecBuffer::with_data(vec![1, 2, 3]);
+        let mut s = SingletonBuffer::new('a');
+
+        let notified = Arc::new(Mutex::new(0));
+        let _obs = s.get_port().add_notify_fn({
+            let notified = notified.clone();
+            move |_| *notified.lock().unwrap() += 1
+        });
+        let n = *notified.lock().unwrap();
+
+        {
+            let _tx = Transaction::begin();
+            a.remove(0);
+            a.push(4);
+            s.set('b');
+
+            let inner = Transaction::begin();
+            s.set('c');
+            inner.commit();
+            // dropped without commit
+        }
+
+        assert_eq!(*a.get_port().get_view().unwrap().read().unwrap(), vec![1, 2, 3]);
+        assert_eq!(s.get(), 'a');
+
+        crate::view::port::UpdateTask::update(&s.get_port().0);
+        assert_eq!(*notified.lock().unwrap(), n);
+    }
+
+    #[test]
+    #[should_panic(expected = "transaction closed before the ones inside of it")]
+    fn transaction_order() {
+        let outer = Transaction::begin();
+        let _inner = Transaction::begin();
+        outer.commit();
+    }
+}
@@ -0,0 +1,113 @@
+use {
+    crate::view::{
+        channel::{singleton_channel, ChannelSender},
+        port::UpdateTask,
+        OuterViewPort, UpdateScheduler, View,
+    },
+    async_std::task,
+    std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+impl<V: View + ?Sized + 'static> OuterViewPort<V>
+where
+    V::Msg: Clone,
+{
+    /// Spawn a background task which keeps this port up to date:
+    /// whenever a broadcast this port depends on receives new
+    /// notifications, they are pushed downstream to all observers.
+    ///
+    /// The task is cancelled when the returned handle is dropped.
+    pub fn spawn_updater(&self) -> Updater {
+        let (tx, rx) = singleton_channel::<()>();
+        let signal = Arc::new(tx);
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let scheduler = UpdateScheduler::new();
+        scheduler.register(self);
+
+        let port = self.0.clone();
+        task::spawn({
+            let signal = signal.clone();
+            let cancelled = cancelled.clone();
+            async move {
+                loop {
+                    // register again on every iteration to pick up
+                    // update hooks which were added in the meantime
+                    port.add_wake_signal(&signal);
+                    scheduler.run_until_stable();
+
+                    if rx.recv().await.is_none() || cancelled.load(Ordering::SeqCst) {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Updater { signal, cancelled }
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+/// Handle of a task spawned by `OuterViewPort::spawn_updater()`
+pub struct Updater {
+    signal: Arc<ChannelSender<Option<()>>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Drop for Updater {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.signal.send(());
+    }
+}
+
+//<<<<>>>><<>><><<>><<<*>>><<>><><<>><<<<>>>>
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::buffer::vec::*,
+        std::time::Duration,
+    };
+
+    async fn wait_for(cond: impl Fn() -> bool) {
+        for _ in 0..200 {
+            if cond() {
+                return;
+            }
+            async_std::task::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("timeout");
+    }
+
+    #[async_std::test]
+    async fn updater1() {
+        let mut src = VecBuffer::<i32>::new();
+        let dst = VecBuffer::new();
+        let _keepalive = dst.attach_to(src.get_port().to_list().map(|x| x * 2));
+
+        let updater = dst.get_port().spawn_updater();
+
+        src.push(1);
+        src.push(2);
+        wait_for(|| dst.len() == 2).await;
+        assert_eq!(dst.get(1), 4);
+
+        src.remove(0);
+        wait_for(|| dst.len() == 1).await;
+        assert_eq!(dst.get(0), 4);
+
+        drop(updater);
+        async_std::task::sleep(Duration::from_millis(20)).await;
+
+        src.push(3);
+        async_std::task::sleep(Duration::from_millis(20)).await;
+        assert_eq!(dst.len(), 1);
+    }
+}